@@ -1,172 +1,145 @@
-use leptos::{create_rw_signal, provide_context, update, RwSignal, SignalUpdate};
-use leptos_hotkeys::{use_hotkeys, use_hotkeys_context, use_hotkeys_scoped, HotkeysContext};
+use leptos::{
+    create_rw_signal, on_cleanup, provide_context, update, Callable, RwSignal, SignalGetUntracked,
+    SignalUpdate, SignalWithUntracked,
+};
+use leptos_hotkeys::{use_hotkeys, use_hotkeys_context, use_hotkeys_scoped};
 
-use crate::{
+use sudoku_core::{
     actions::{
-        check_all_visible_doubles, check_constraints, check_triples, clear_digit_if_selected,
-        handle_arrow, load_random_sudoku, place_all_hidden_singles, place_all_visible_singles,
-        solve_sudoku, toggle_choice_if_selected, toggle_digit_if_selected, verify_sudoku,
+        apply_digit, check_selected_cells, clear_colors_if_selected, clear_digit_if_selected,
+        reveal_active_cell, toggle_color_if_selected,
+    },
+    bindings::{
+        get_color_hotkeys, get_generator_hotkeys, get_solver_hotkeys, CHECK_CELLS_KEY,
+        CLEAR_COLORS_KEY, REVEAL_CELL_KEY,
     },
-    generator::Difficulty,
+    solution_path::SolutionPath,
     state::{DigitMode, GameState},
     sudoku_data::SudokuData,
-    Result,
 };
 
-pub struct Hotkey {
-    pub key: &'static str,
-    pub action: &'static str,
-    pub on_click: fn(&mut SudokuData) -> Result<String>,
-}
+use crate::actions::{give_hint, handle_arrow, load_random_sudoku, run_solver_hotkey};
+use crate::worker::SolverWorker;
 
-pub struct GeneratorHotkey {
-    pub key: &'static str,
-    pub action: &'static str,
-    pub difficulty: Difficulty,
+/// The scope of the hotkeys of the single board. It is only active while
+/// that board is shown, so that the keys typed into the Samurai page do not
+/// change the board that is hidden behind it.
+const SCOPE: &str = "sudoku";
+
+pub fn enable_board_hotkeys() {
+    let context = use_hotkeys_context();
+    context.enable_scope.call(SCOPE.to_string());
+    on_cleanup(move || context.disable_scope.call(SCOPE.to_string()));
 }
 
 #[allow(clippy::module_name_repetitions)]
-pub fn setup_hotkeys(game_state: RwSignal<GameState>, sudoku: RwSignal<SudokuData>) {
-    setup_placement_hotkeys(game_state, sudoku);
-    setup_solver_hotkeys(game_state, sudoku);
-    setup_movement_hotkeys(game_state);
-
-    let HotkeysContext {
-        toggle_scope,
-        enable_scope,
-        ..
-    } = use_hotkeys_context();
+pub fn setup_hotkeys(
+    game_state: RwSignal<GameState>,
+    sudoku: RwSignal<SudokuData>,
+    path: RwSignal<SolutionPath>,
+    worker: SolverWorker,
+) {
     let digit_mode = create_rw_signal(DigitMode::Value);
     provide_context(digit_mode);
-    enable_scope("place_digits".into());
 
-    use_hotkeys!(("Tab") => move |()| {
-        toggle_scope("toggle_choices".into());
-        toggle_scope("place_digits".into());
+    setup_placement_hotkeys(game_state, sudoku, digit_mode);
+    setup_solver_hotkeys(game_state, sudoku, path, worker);
+    setup_color_hotkeys(game_state, sudoku);
+    setup_movement_hotkeys(game_state, sudoku);
+
+    use_hotkeys!(("Tab", SCOPE) => move |()| {
         digit_mode.update(DigitMode::toggle);
     });
 }
 
-fn setup_placement_hotkeys(game_state: RwSignal<GameState>, sudoku: RwSignal<SudokuData>) {
-    for i in 1..=9 {
-        setup_digit_hotkey(i, game_state, sudoku);
-        setup_digit_choice_hotkey(i, game_state, sudoku);
+fn setup_placement_hotkeys(
+    game_state: RwSignal<GameState>,
+    sudoku: RwSignal<SudokuData>,
+    digit_mode: RwSignal<DigitMode>,
+) {
+    for key in '0'..='9' {
+        setup_digit_hotkey(key, game_state, sudoku, digit_mode);
     }
-    use_hotkeys!((format!("Escape,Backspace")) => move |()| {
+    use_hotkeys!((format!("Escape,Backspace"), SCOPE) => move |()| {
         update!(|game_state, sudoku| {
             clear_digit_if_selected(game_state, sudoku);
         });
     });
 }
 
-#[allow(clippy::module_name_repetitions)]
-pub fn get_solver_hotkeys() -> Vec<Hotkey> {
-    vec![
-        Hotkey {
-            key: "A",
-            action: "SINGLES",
-            on_click: place_all_visible_singles,
-        },
-        Hotkey {
-            key: "S",
-            action: "HIDDEN",
-            on_click: place_all_hidden_singles,
-        },
-        Hotkey {
-            key: "D",
-            action: "DOUBLES",
-            on_click: check_all_visible_doubles,
-        },
-        Hotkey {
-            key: "F",
-            action: "TRIPLES",
-            on_click: check_triples,
-        },
-        Hotkey {
-            key: "G",
-            action: "CONSTRAINTS",
-            on_click: check_constraints,
-        },
-        Hotkey {
-            key: "H",
-            action: "SOLVE",
-            on_click: solve_sudoku,
-        },
-        Hotkey {
-            key: "J",
-            action: "VERIFY",
-            on_click: verify_sudoku,
-        },
-    ]
-}
-
-#[allow(clippy::module_name_repetitions)]
-pub fn get_generator_hotkeys() -> Vec<GeneratorHotkey> {
-    vec![
-        GeneratorHotkey {
-            key: "B",
-            action: "HARD",
-            difficulty: Difficulty::Hard,
-        },
-        GeneratorHotkey {
-            key: "N",
-            action: "17 CLUE",
-            difficulty: Difficulty::Clue17,
-        },
-        GeneratorHotkey {
-            key: "M",
-            action: "EXTREME",
-            difficulty: Difficulty::Extreme,
-        },
-    ]
-}
-
-fn setup_solver_hotkeys(game_state: RwSignal<GameState>, sudoku: RwSignal<SudokuData>) {
-    let apply_and_show = move |f: fn(&mut SudokuData) -> crate::Result<String>| {
-        move |()| {
+fn setup_color_hotkeys(game_state: RwSignal<GameState>, sudoku: RwSignal<SudokuData>) {
+    for shortcut in get_color_hotkeys() {
+        use_hotkeys!((shortcut.key, SCOPE) => move |()| {
             update!(|game_state, sudoku| {
-                game_state.show_result(f(sudoku));
+                toggle_color_if_selected(game_state, sudoku, shortcut.color);
             });
-        }
-    };
+        });
+    }
+    use_hotkeys!((CLEAR_COLORS_KEY, SCOPE) => move |()| {
+        update!(|game_state, sudoku| {
+            clear_colors_if_selected(game_state, sudoku);
+        });
+    });
+}
 
+fn setup_solver_hotkeys(
+    game_state: RwSignal<GameState>,
+    sudoku: RwSignal<SudokuData>,
+    path: RwSignal<SolutionPath>,
+    worker: SolverWorker,
+) {
     for shortcut in get_solver_hotkeys() {
-        use_hotkeys!((shortcut.key) => apply_and_show(shortcut.on_click));
+        use_hotkeys!((shortcut.key, SCOPE) => move |()| {
+            run_solver_hotkey(shortcut, game_state, sudoku, path, worker);
+        });
     }
+    use_hotkeys!((REVEAL_CELL_KEY, SCOPE) => move |()| {
+        give_hint(game_state, sudoku, worker, reveal_active_cell);
+    });
+    use_hotkeys!((CHECK_CELLS_KEY, SCOPE) => move |()| {
+        give_hint(game_state, sudoku, worker, check_selected_cells);
+    });
     for shortcut in get_generator_hotkeys() {
-        use_hotkeys!((shortcut.key) => move |()| load_random_sudoku(shortcut.difficulty));
+        use_hotkeys!((shortcut.key, SCOPE) => move |()| {
+            load_random_sudoku(shortcut.difficulty, game_state, worker);
+        });
     }
 }
 
-fn setup_movement_hotkeys(game_state: RwSignal<GameState>) {
-    setup_arrow_hotkey("ArrowRight", (0, 1), game_state);
-    setup_arrow_hotkey("ArrowLeft", (0, -1), game_state);
-    setup_arrow_hotkey("ArrowUp", (-1, 0), game_state);
-    setup_arrow_hotkey("ArrowDown", (1, 0), game_state);
+fn setup_movement_hotkeys(game_state: RwSignal<GameState>, sudoku: RwSignal<SudokuData>) {
+    setup_arrow_hotkey("ArrowRight", (0, 1), game_state, sudoku);
+    setup_arrow_hotkey("ArrowLeft", (0, -1), game_state, sudoku);
+    setup_arrow_hotkey("ArrowUp", (-1, 0), game_state, sudoku);
+    setup_arrow_hotkey("ArrowDown", (1, 0), game_state, sudoku);
 }
 
-fn setup_arrow_hotkey(name: &str, direction: (i32, i32), game_state: RwSignal<GameState>) {
-    use_hotkeys!((name) => move |()| {
-        handle_arrow(&game_state, direction);
+fn setup_arrow_hotkey(
+    name: &str,
+    direction: (i32, i32),
+    game_state: RwSignal<GameState>,
+    sudoku: RwSignal<SudokuData>,
+) {
+    use_hotkeys!((name, SCOPE) => move |()| {
+        handle_arrow(&game_state, sudoku.with_untracked(SudokuData::size), direction, false);
     });
-}
-
-fn setup_digit_hotkey(i: usize, game_state: RwSignal<GameState>, sudoku: RwSignal<SudokuData>) {
-    use_hotkeys!((format!("{i}"), "place_digits") => move |()| {
-        update!(|game_state, sudoku| {
-            toggle_digit_if_selected(game_state, sudoku, i as u8);
-        });
+    use_hotkeys!((format!("shift+{name}"), SCOPE) => move |()| {
+        handle_arrow(&game_state, sudoku.with_untracked(SudokuData::size), direction, true);
     });
 }
 
-fn setup_digit_choice_hotkey(
-    i: usize,
+fn setup_digit_hotkey(
+    key: char,
     game_state: RwSignal<GameState>,
     sudoku: RwSignal<SudokuData>,
+    digit_mode: RwSignal<DigitMode>,
 ) {
-    use_hotkeys!((format!("{i}"), "toggle_choices") => move |()| {
+    use_hotkeys!((key.to_string(), SCOPE) => move |()| {
+        let Some(digit) = sudoku.with_untracked(|sudoku| sudoku.size().parse_digit(key)) else {
+            return;
+        };
+        let mode = digit_mode.get_untracked();
         update!(|game_state, sudoku| {
-            toggle_choice_if_selected(game_state, sudoku, i as u8);
+            apply_digit(mode, game_state, sudoku, digit);
         });
     });
 }
@@ -38,28 +38,23 @@ use leptos::SignalSet;
 use leptos_hotkeys::{provide_hotkeys_context, scopes, HotkeysContext};
 use leptos_use::use_cookie_with_options;
 use leptos_use::UseCookieOptions;
-use state::GameState;
-use sudoku::SudokuGame;
+use sudoku::{SamuraiGame, SudokuGame};
+use worker::provide_solver_worker;
 
 use leptos::{component, create_node_ref, html, mount_to_body, view, IntoView};
 use leptos_meta::provide_meta_context;
 use leptos_router::{Route, Router, Routes, TrailingSlash};
-use rstest as _;
-use serde_json as _;
 
 mod actions;
-mod error;
-mod generator;
 mod hotkeys;
-mod state;
 mod sudoku;
-mod sudoku_data;
 mod ui;
 mod util;
+mod worker;
 
-pub use error::Error;
-pub use error::Result;
-use sudoku_data::SudokuData;
+use sudoku_core::solution_path::SolutionPath;
+use sudoku_core::state::GameState;
+use sudoku_core::sudoku_data::SudokuData;
 
 #[component]
 fn App() -> impl IntoView {
@@ -71,7 +66,10 @@ fn App() -> impl IntoView {
     let (sudoku_data, sudoku_data_callback) = setup_sudoku_data();
     let (game_state, game_state_callback) = setup_game_state();
 
-    setup_hotkeys(game_state, sudoku_data);
+    let path = create_rw_signal(SolutionPath::default());
+    provide_context(path);
+    let worker = provide_solver_worker();
+    setup_hotkeys(game_state, sudoku_data, path, worker);
 
     view! {
         {sudoku_data_callback}
@@ -86,6 +84,7 @@ fn App() -> impl IntoView {
                             trailing_slash=TrailingSlash::Exact
                             view=SudokuGame
                         />
+                        <Route path="/sudoku_solver_wasm/samurai" view=SamuraiGame />
                         <Route path="/*any" view=move || view! { <p>"Page not found"</p> } />
                     </Routes>
                 </main>
@@ -100,6 +99,8 @@ fn setup_sudoku_data() -> (RwSignal<SudokuData>, impl Fn()) {
             "sudoku_data",
             UseCookieOptions::default().path("/"),
         );
+    // a cookie that does not hold a valid board, e.g. one with rows that do
+    // not fit its variant, fails to decode and gives the default board
     let sudoku_data = create_rw_signal(sudoku_data_cookie.get_untracked().unwrap_or_default());
     provide_context(sudoku_data);
     (sudoku_data, move || {
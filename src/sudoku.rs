@@ -1,27 +1,54 @@
-use crate::actions::update_from_sudoku;
-use crate::state::GameState;
-use crate::sudoku_data::{Cell, SudokuData};
+use crate::actions::show_if_not_started;
+use crate::hotkeys::enable_board_hotkeys;
 use crate::ui::{
-    DarkModeToggle, DigitDisplay, GeneratorShortcuts, KeyboardShortcuts, SudokuDisplay,
+    BenchmarkPanel, ColorPalette, DarkModeToggle, DigitDisplay, GeneratorShortcuts,
+    HighlightSettings, KeyboardShortcuts, LimitedSolve, ReplayControls, SolutionPathPanel,
+    SudokuDisplay,
 };
-use crate::util::{sudokus_equal, unwrap_or_panic, unwrap_params, SudokuParams};
+use crate::util::{unwrap_or_panic, unwrap_puzzle, unwrap_samurai, SudokuParams};
+use crate::worker::SolverWorker;
 
 use leptos::{
-    component, create_memo, use_context, view, IntoView, RwSignal, SignalUpdate, SignalWith,
+    component, create_memo, create_rw_signal,
+    ev::{KeyboardEvent, MouseEvent},
+    update, use_context, view, CollectView, IntoView, RwSignal, SignalGetUntracked, SignalSet,
+    SignalUpdate, SignalWith, SignalWithUntracked,
 };
 use leptos_router::use_query;
+use sudoku_core::actions::toggle_choice_color;
+use sudoku_core::constraint::Pos;
+use sudoku_core::lines::{Arrow, DotKind};
+use sudoku_core::protocol::{Operation, Outcome};
+use sudoku_core::puzzle::Puzzle;
+use sudoku_core::samurai::{grids_at, Samurai, SIDE};
+use sudoku_core::size::{Size, MAX_DIGITS};
+use sudoku_core::solution_path::SolutionPath;
+use sudoku_core::state::{GameState, Highlight, Tally};
+use sudoku_core::sudoku_data::{Cell, CellMarks, Color, PencilMark, SudokuData};
+use sudoku_core::techniques::update_from_digits;
 
 #[component]
 pub fn SudokuGame() -> impl IntoView {
+    enable_board_hotkeys();
     let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let path = unwrap_or_panic(use_context::<RwSignal<SolutionPath>>());
+    let worker = unwrap_or_panic(use_context::<SolverWorker>());
     let params = use_query::<SudokuParams>();
-    let sudoku = move || params.with(unwrap_params);
     let update = move |data: &mut SudokuData| {
-        if !sudokus_equal(&data.fixed_sudoku(), &sudoku())
-            && rust_sudoku_solver::solve(sudoku()).is_ok()
-        {
-            data.clear();
-            update_from_sudoku(data, &sudoku(), true);
+        let Puzzle { givens, variant } = params.with(unwrap_puzzle);
+        if data.fixed_digits() != givens || data.variant != variant {
+            *data = SudokuData::new(variant);
+            update_from_digits(data, &givens, true);
+            path.update(SolutionPath::clear);
+            game_state.update(|state| state.tally = Tally::default());
+            check_puzzle(
+                Operation::verify(data),
+                givens,
+                game_state,
+                sudoku_data,
+                worker,
+            );
         }
     };
     view! {
@@ -36,8 +63,14 @@ pub fn SudokuGame() -> impl IntoView {
                     <DigitDisplay />
                     <KeyboardShortcuts />
                     <GeneratorShortcuts />
+                    <ColorPalette />
+                    <HighlightSettings />
+                    <LimitedSolve />
+                    <SolutionPathPanel />
                 </div>
+                <ReplayControls />
                 <SudokuDisplay />
+                <BenchmarkPanel />
             </div>
         </div>
     }
@@ -45,136 +78,584 @@ pub fn SudokuGame() -> impl IntoView {
 
 #[component]
 fn SudokuGrid() -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let size = create_memo(move |_| sudoku_data.with(SudokuData::size));
+    let jigsaw =
+        create_memo(move |_| sudoku_data.with(|sudoku| !sudoku.variant.regions.is_empty()));
+    let stop_dragging = move |_| game_state.update(|state| state.dragging = false);
     view! {
         <div
+            on:mouseup=stop_dragging
+            on:mouseleave=stop_dragging
             style="width: min(60vw, 60vh);height: min(60vw, 60vh);font-family: 'Source Sans Pro', serif"
-            class="bg-white border-gray-800 dark:bg-black border-4 shadow-lg flex flex-col m-auto lining-nums fade-dark"
+            class="relative bg-white border-gray-800 dark:bg-black border-4 shadow-lg flex flex-col m-auto lining-nums fade-dark"
         >
-            <SudokuRow idx=0 />
-            <SudokuRow idx=1 />
-            <SudokuRow idx=2 />
+            {move || {
+                let size = size();
+                if jigsaw() {
+                    (0..size.digits())
+                        .map(|row| view! { <JigsawRow size=size row=row /> })
+                        .collect_view()
+                } else {
+                    (0..size.box_cols())
+                        .map(|idx| view! { <SudokuRow size=size idx=idx /> })
+                        .collect_view()
+                }
+            }}
+            <LineOverlay />
+        </div>
+    }
+}
+
+fn centre((row, col): Pos) -> (f64, f64) {
+    (col as f64 + 0.5, row as f64 + 0.5)
+}
+
+fn polyline(points: impl IntoIterator<Item = (f64, f64)>) -> String {
+    points
+        .into_iter()
+        .map(|(x, y)| format!("{x},{y}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+const ARROW_CIRCLE: f64 = 0.4;
+
+fn arrow_paths(arrow: &Arrow) -> (String, String) {
+    let points: Vec<_> = arrow.cells.iter().copied().map(centre).collect();
+    let ((x0, y0), (x1, y1)) = (points[0], points[1]);
+    let length = (x1 - x0).hypot(y1 - y0);
+    let start = (
+        x0 + (x1 - x0) * ARROW_CIRCLE / length,
+        y0 + (y1 - y0) * ARROW_CIRCLE / length,
+    );
+    let shaft = polyline(std::iter::once(start).chain(points[1..].iter().copied()));
+
+    let ((xa, ya), (xb, yb)) = (points[points.len() - 2], points[points.len() - 1]);
+    let angle = (yb - ya).atan2(xb - xa);
+    let barb = |turn: f64| {
+        (
+            0.3f64.mul_add((angle + turn).cos(), xb),
+            0.3f64.mul_add((angle + turn).sin(), yb),
+        )
+    };
+    let head = polyline([barb(2.5), (xb, yb), barb(-2.5)]);
+    (shaft, head)
+}
+
+#[component]
+fn LineOverlay() -> impl IntoView {
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let variant = create_memo(move |_| sudoku_data.with(|sudoku| sudoku.variant.clone()));
+    move || {
+        let variant = variant();
+        let n = variant.size.digits();
+        let thermos = variant
+            .thermos
+            .iter()
+            .map(|thermo| {
+                let (x, y) = centre(thermo.cells[0]);
+                let points = polyline(thermo.cells.iter().copied().map(centre));
+                view! {
+                    <circle cx=x cy=y r="0.35" class="fill-slate-300 dark:fill-zinc-700" />
+                    <polyline
+                        points=points
+                        fill="none"
+                        stroke-width="0.3"
+                        stroke-linecap="round"
+                        stroke-linejoin="round"
+                        class="stroke-slate-300 dark:stroke-zinc-700"
+                    />
+                }
+            })
+            .collect_view();
+        let arrows = variant
+            .arrows
+            .iter()
+            .map(|arrow| {
+                let (x, y) = centre(arrow.cells[0]);
+                let (shaft, head) = arrow_paths(arrow);
+                view! {
+                    <circle cx=x cy=y r=ARROW_CIRCLE fill="none" stroke-width="0.05" />
+                    <polyline points=shaft fill="none" stroke-width="0.05" />
+                    <polyline points=head fill="none" stroke-width="0.05" />
+                }
+            })
+            .collect_view();
+        let dots = variant
+            .dots
+            .iter()
+            .map(|dot| {
+                let (x, y) = dot.centre();
+                let class = match dot.kind {
+                    DotKind::White => "fill-white stroke-gray-800",
+                    DotKind::Black => "fill-gray-800 stroke-gray-800 dark:fill-gray-400",
+                };
+                view! { <circle cx=x cy=y r="0.12" stroke-width="0.03" class=class /> }
+            })
+            .collect_view();
+        view! {
+            <svg
+                class="absolute inset-0 w-full h-full pointer-events-none z-20 stroke-gray-500 fade-dark"
+                viewBox=format!("0 0 {n} {n}")
+            >
+                {thermos}
+                {arrows}
+                {dots}
+            </svg>
+        }
+    }
+}
+
+#[component]
+fn JigsawRow(size: Size, row: usize) -> impl IntoView {
+    view! {
+        <div class="flex flex-1">
+            {(0..size.digits())
+                .map(|col| view! { <SudokuCell size=size row=row col=col /> })
+                .collect_view()}
         </div>
     }
 }
 
 #[component]
-fn SudokuRow(idx: usize) -> impl IntoView {
+fn SudokuRow(size: Size, idx: usize) -> impl IntoView {
     view! {
-        <div class="basis-1/3 flex w-full">
-            <SudokuBox idx=3 * idx />
-            <SudokuBox idx=3 * idx + 1 />
-            <SudokuBox idx=3 * idx + 2 />
+        <div class="flex-1 flex w-full">
+            {(0..size.box_rows())
+                .map(|stack| view! { <SudokuBox size=size band=idx stack=stack /> })
+                .collect_view()}
         </div>
     }
 }
 
 #[component]
-fn SudokuBox(idx: usize) -> impl IntoView {
+fn SudokuBox(size: Size, band: usize, stack: usize) -> impl IntoView {
     view! {
-        <div class="border-gray-800 border-2 z-10 flex flex-col basis-1/3">
-            <SudokuBoxRow row=idx / 3 * 3 idx=idx % 3 />
-            <SudokuBoxRow row=idx / 3 * 3 + 1 idx=idx % 3 />
-            <SudokuBoxRow row=idx / 3 * 3 + 2 idx=idx % 3 />
+        <div class="border-gray-800 border-2 z-10 flex flex-col flex-1">
+            {(0..size.box_rows())
+                .map(|i| {
+                    view! { <SudokuBoxRow size=size row=band * size.box_rows() + i stack=stack /> }
+                })
+                .collect_view()}
         </div>
     }
 }
 
 #[component]
-fn SudokuBoxRow(row: usize, idx: usize) -> impl IntoView {
+fn SudokuBoxRow(size: Size, row: usize, stack: usize) -> impl IntoView {
     view! {
-        <div class="flex basis-1/3">
-            <SudokuCell row=row col=3 * idx />
-            <SudokuCell row=row col=3 * idx + 1 />
-            <SudokuCell row=row col=3 * idx + 2 />
+        <div class="flex flex-1">
+            {(0..size.box_cols())
+                .map(|j| {
+                    view! { <SudokuCell size=size row=row col=stack * size.box_cols() + j /> }
+                })
+                .collect_view()}
         </div>
     }
 }
 
+fn font_size(size: Size, nine_by_nine: f64) -> String {
+    let scaled = nine_by_nine * 9.0 / size.digits() as f64;
+    format!("font-size: min({scaled}vw, {scaled}vh);")
+}
+
 #[component]
-fn SudokuCell(row: usize, col: usize) -> impl IntoView {
+fn SudokuCell(size: Size, row: usize, col: usize) -> impl IntoView {
     let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
     let set_game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
-    let on_click = move |_| {
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let on_mouse_down = move |ev: MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        let (add, extend) = (ev.ctrl_key() || ev.meta_key(), ev.shift_key());
         set_game_state.update(|state| {
-            state.active_cell = Some((row, col));
+            if add {
+                state.toggle_selection((row, col));
+            } else if extend {
+                state.extend_selection((row, col));
+            } else {
+                state.select((row, col));
+            }
+            state.dragging = true;
         });
     };
+    let on_mouse_enter = move |_| {
+        if game_state.with_untracked(|state| state.dragging) {
+            set_game_state.update(|state| state.extend_selection((row, col)));
+        }
+    };
+    let highlight = move || sudoku_data.with(|sudoku| game_state().highlight(sudoku, row, col));
+    let placed = move || game_state.with(|state| state.changes.is_placed((row, col)));
     let class = move || {
-        if game_state().is_active_cell(row, col) {
-            "sudoku-cell hover:bg-cerulean-blue-300 dark:hover:bg-zinc-800 bg-gray-300 dark:bg-zinc-900"
+        if placed() {
+            format!("{} flash-placed", cell_class(highlight()))
         } else {
-            "sudoku-cell hover:bg-cerulean-blue-100 dark:hover:bg-zinc-900"
+            cell_class(highlight()).to_string()
         }
     };
+    let color_class = move || {
+        sudoku_data.with(|sudoku| {
+            sudoku
+                .marks(row, col)
+                .color
+                .map_or_else(String::new, |color| {
+                    format!(
+                        "absolute inset-0 opacity-40 pointer-events-none {}",
+                        color.class()
+                    )
+                })
+        })
+    };
     view! {
-        <div style="font-size: min(5vw, 5vh);" class=class on:click=on_click>
-            <CellInside row=row col=col />
+        <div
+            style=font_size(size, 5.0)
+            class=class
+            on:mousedown=on_mouse_down
+            on:mouseenter=on_mouse_enter
+        >
+            <div class=color_class />
+            <RegionBorders row=row col=col />
+            <DiagonalMarks row=row col=col />
+            <CageOutline size=size row=row col=col />
+            <CellInside size=size row=row col=col />
         </div>
     }
 }
 
+const REGION_SIDES: [&str; 4] = ["border-t-2", "border-r-2", "border-b-2", "border-l-2"];
+
+#[component]
+fn RegionBorders(row: usize, col: usize) -> impl IntoView {
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let borders = create_memo(move |_| {
+        sudoku_data.with(|sudoku| {
+            (!sudoku.variant.regions.is_empty()).then(|| sudoku.variant.region_borders(row, col))
+        })
+    });
+    move || {
+        borders().map(|borders| {
+            let sides: Vec<_> = borders
+                .iter()
+                .zip(REGION_SIDES)
+                .filter_map(|(&border, side)| border.then_some(side))
+                .collect();
+            let class = format!(
+                "absolute inset-0 pointer-events-none z-10 border-gray-800 {}",
+                sides.join(" ")
+            );
+            view! { <div class=class /> }
+        })
+    }
+}
+
+#[component]
+fn DiagonalMarks(row: usize, col: usize) -> impl IntoView {
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let diagonals =
+        create_memo(move |_| sudoku_data.with(|sudoku| sudoku.variant.diagonals_at(row, col)));
+    move || {
+        let (main, anti) = diagonals();
+        (main || anti).then(|| {
+            view! {
+                <svg
+                    class="absolute inset-0 w-full h-full pointer-events-none stroke-slate-300 dark:stroke-zinc-800 fade-dark"
+                    viewBox="0 0 1 1"
+                    preserveAspectRatio="none"
+                >
+                    {main
+                        .then(|| {
+                            view! {
+                                <line
+                                    x1="0"
+                                    y1="0"
+                                    x2="1"
+                                    y2="1"
+                                    vector-effect="non-scaling-stroke"
+                                    stroke-width="2"
+                                />
+                            }
+                        })}
+                    {anti
+                        .then(|| {
+                            view! {
+                                <line
+                                    x1="1"
+                                    y1="0"
+                                    x2="0"
+                                    y2="1"
+                                    vector-effect="non-scaling-stroke"
+                                    stroke-width="2"
+                                />
+                            }
+                        })}
+                </svg>
+            }
+        })
+    }
+}
+
+const CAGE_SIDES: [(&str, &str); 4] = [
+    ("top-[3px] border-t", "top-0"),
+    ("right-[3px] border-r", "right-0"),
+    ("bottom-[3px] border-b", "bottom-0"),
+    ("left-[3px] border-l", "left-0"),
+];
+
+#[component]
+fn CageOutline(size: Size, row: usize, col: usize) -> impl IntoView {
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let borders =
+        create_memo(move |_| sudoku_data.with(|sudoku| sudoku.variant.cage_borders(row, col)));
+    let label = create_memo(move |_| {
+        sudoku_data.with(|sudoku| {
+            sudoku
+                .variant
+                .cage_at(row, col)
+                .filter(|cage| cage.anchor() == Some((row, col)))
+                .map(|cage| cage.sum)
+        })
+    });
+    let class = move || {
+        borders().map_or_else(String::new, |borders| {
+            let sides: Vec<_> = borders
+                .iter()
+                .zip(CAGE_SIDES)
+                .map(|(&border, (on, off))| if border { on } else { off })
+                .collect();
+            format!(
+                "absolute pointer-events-none border-dashed border-gray-500 {}",
+                sides.join(" ")
+            )
+        })
+    };
+    view! {
+        <div class=class />
+        {move || {
+            label()
+                .map(|sum| {
+                    view! {
+                        <p
+                            style=font_size(size, 1.1)
+                            class="absolute top-[1px] left-[1px] px-[1px] z-10 leading-none pointer-events-none bg-white dark:bg-black dark:text-gray-400 fade-dark"
+                        >
+                            {sum}
+                        </p>
+                    }
+                })
+        }}
+    }
+}
+
+const fn cell_class(highlight: Highlight) -> &'static str {
+    match highlight {
+        Highlight::Active => {
+            "sudoku-cell hover:bg-cerulean-blue-300 dark:hover:bg-zinc-800 bg-gray-300 dark:bg-zinc-900"
+        }
+        Highlight::Selected => {
+            "sudoku-cell hover:bg-cerulean-blue-300 dark:hover:bg-zinc-800 bg-cerulean-blue-300 dark:bg-zinc-800"
+        }
+        Highlight::SameDigit => {
+            "sudoku-cell hover:bg-cerulean-blue-300 dark:hover:bg-zinc-800 bg-cerulean-blue-200 dark:bg-cerulean-blue-950"
+        }
+        Highlight::Peer => {
+            "sudoku-cell hover:bg-cerulean-blue-100 dark:hover:bg-zinc-900 bg-slate-100 dark:bg-zinc-950"
+        }
+        Highlight::None => "sudoku-cell hover:bg-cerulean-blue-100 dark:hover:bg-zinc-900",
+    }
+}
+
 #[component]
-fn CellChoiceRow(idx: usize, choices: [bool; 9]) -> impl IntoView {
+fn CellChoiceRow(
+    size: Size,
+    row: usize,
+    col: usize,
+    idx: usize,
+    choices: [bool; MAX_DIGITS],
+) -> impl IntoView {
     view! {
-        <div class="flex flex-row basis-1/3">
-            <CellChoice idx=3 * idx show=choices[3 * idx] />
-            <CellChoice idx=3 * idx + 1 show=choices[3 * idx + 1] />
-            <CellChoice idx=3 * idx + 2 show=choices[3 * idx + 2] />
+        <div class="flex flex-row flex-1">
+            {(0..size.box_cols())
+                .map(|j| {
+                    let digit = idx * size.box_cols() + j;
+                    view! {
+                        <CellChoice size=size row=row col=col idx=digit show=choices[digit] />
+                    }
+                })
+                .collect_view()}
         </div>
     }
 }
 
 #[component]
-fn CellChoice(idx: usize, show: bool) -> impl IntoView {
+fn CellChoice(size: Size, row: usize, col: usize, idx: usize, show: bool) -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let digit = idx as u8 + 1;
+    let highlighted =
+        move || sudoku_data.with(|sudoku| game_state().is_highlighted_choice(sudoku, digit));
+    let color_class = move || {
+        sudoku_data
+            .with(|sudoku| sudoku.marks(row, col).choice_colors[idx].map_or("", Color::class))
+    };
+    // a candidate a technique just ruled out is struck through while it fades
+    let eliminated =
+        move || !show && game_state.with(|state| state.changes.is_eliminated((row, col), digit));
+    let class = move || {
+        let highlight = if eliminated() {
+            "line-through text-red-500 fade-out"
+        } else if highlighted() {
+            "text-cerulean-blue-600 font-bold"
+        } else {
+            ""
+        };
+        format!(
+            "min-h-0 leading-none rounded-sm {highlight} {}",
+            color_class()
+        )
+    };
+    // handled on mouse down, before the cell itself changes the selection
+    let on_mouse_down = move |ev: MouseEvent| {
+        if ev.button() == 0 && ev.alt_key() {
+            ev.stop_propagation();
+            update!(|game_state, sudoku_data| {
+                toggle_choice_color(game_state, sudoku_data, (row, col), digit);
+            });
+            return;
+        }
+        let plain_click = ev.button() == 0 && !ev.ctrl_key() && !ev.meta_key() && !ev.shift_key();
+        if plain_click && game_state.with_untracked(|state| state.is_active_cell(row, col)) {
+            ev.stop_propagation();
+            sudoku_data.update(|sudoku| sudoku.toggle_choice(row, col, digit));
+        }
+    };
+    let on_context_menu = move |ev: MouseEvent| {
+        ev.prevent_default();
+        update!(|game_state, sudoku_data| {
+            game_state.select((row, col));
+            sudoku_data.toggle_choice(row, col, digit);
+        });
+    };
     view! {
-        <div class="w-1/3 basis-1/3 flex items-center justify-center">
-            <p class="min-h-0 leading-none">
-                {if show { (idx + 1).to_string() } else { String::new() }}
+        <div
+            class="flex-1 flex items-center justify-center"
+            on:mousedown=on_mouse_down
+            on:contextmenu=on_context_menu
+        >
+            <p class=class>
+                {move || {
+                    if show || eliminated() {
+                        size.digit_char(digit).to_string()
+                    } else {
+                        String::new()
+                    }
+                }}
             </p>
         </div>
     }
 }
 
-fn render_choices(choices: &[bool; 9]) -> leptos::HtmlElement<leptos::html::Div> {
-    if choices.iter().all(|&b| b) {
-        view! { <div class="flex flex-col w-full h-full" /> }
+fn render_choices(
+    choices: &[bool; MAX_DIGITS],
+    marks: &CellMarks,
+    size: Size,
+    row: usize,
+    col: usize,
+) -> leptos::HtmlElement<leptos::html::Div> {
+    // a cell where every digit is still possible shows no candidates, and
+    // pencil marks take their place when there are any, but the grid is kept
+    // so that the candidates remain clickable
+    let has_pencil_marks = marks.has_pencil_marks();
+    let visible = if has_pencil_marks || *choices == size.all_digits() {
+        [false; MAX_DIGITS]
     } else {
-        view! {
-            <div
-                style="font-size: min(1.5vw, 1.5vh);"
-                class="flex flex-col w-full h-full text-slate-500"
-            >
-                <CellChoiceRow idx=0 choices=*choices />
-                <CellChoiceRow idx=1 choices=*choices />
-                <CellChoiceRow idx=2 choices=*choices />
-            </div>
-        }
+        *choices
+    };
+    view! {
+        <div style=font_size(size, 1.5) class="flex flex-col w-full h-full text-slate-500">
+            {(0..size.box_rows())
+                .map(|idx| {
+                    view! { <CellChoiceRow size=size row=row col=col idx=idx choices=visible /> }
+                })
+                .collect_view()}
+            {has_pencil_marks.then(|| render_pencil_marks(marks, size))}
+        </div>
+    }
+}
+
+const CORNER_POSITIONS: [&str; 9] = [
+    "top-0 left-0",
+    "top-0 right-0",
+    "bottom-0 left-0",
+    "bottom-0 right-0",
+    "top-0 left-1/2 -translate-x-1/2",
+    "bottom-0 left-1/2 -translate-x-1/2",
+    "top-1/2 left-0 -translate-y-1/2",
+    "top-1/2 right-0 -translate-y-1/2",
+    "top-1/2 left-1/2 -translate-x-1/2 -translate-y-1/2",
+];
+
+fn marked_digits(marks: &[bool; MAX_DIGITS], size: Size) -> impl Iterator<Item = char> + '_ {
+    marks
+        .iter()
+        .enumerate()
+        .filter(|(_, &marked)| marked)
+        .map(move |(i, _)| size.digit_char(i as u8 + 1))
+}
+
+fn render_pencil_marks(marks: &CellMarks, size: Size) -> leptos::HtmlElement<leptos::html::Div> {
+    let corner = marked_digits(marks.pencil_marks(PencilMark::Corner), size)
+        .zip(CORNER_POSITIONS)
+        .map(|(digit, position)| {
+            view! { <p class=format!("absolute px-[0.2em] leading-none {position}")>{digit}</p> }
+        })
+        .collect_view();
+    let centre: String = marked_digits(marks.pencil_marks(PencilMark::Centre), size).collect();
+    view! {
+        <div class="absolute inset-0 pointer-events-none text-cerulean-blue-700 dark:text-cerulean-blue-400">
+            {corner}
+            <p class="absolute top-1/2 left-1/2 -translate-x-1/2 -translate-y-1/2 leading-none">
+                {centre}
+            </p>
+        </div>
     }
 }
 
 #[component]
-fn CellInside(row: usize, col: usize) -> impl IntoView {
+fn CellInside(size: Size, row: usize, col: usize) -> impl IntoView {
     let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
     let effect = create_memo(move |_| sudoku_data.with(|sudoku| sudoku.get(row, col)));
-    move || effect.with(render_cell)
+    let marks = create_memo(move |_| sudoku_data.with(|sudoku| *sudoku.marks(row, col)));
+    move || effect.with(|cell| marks.with(|marks| render_cell(cell, marks, size, row, col)))
 }
 
-fn render_cell(cell: &Cell) -> leptos::HtmlElement<leptos::html::Div> {
+fn render_cell(
+    cell: &Cell,
+    marks: &CellMarks,
+    size: Size,
+    row: usize,
+    col: usize,
+) -> leptos::HtmlElement<leptos::html::Div> {
     match cell {
-        Cell::Empty { choices } => render_choices(choices),
-        Cell::Value { value, .. } => render_value(&ValueType::Value(*value)),
+        Cell::Empty { choices } => render_choices(choices, marks, size, row, col),
+        Cell::Value { value, .. } => render_value(&ValueType::Value(*value), size),
         Cell::AnimatedValue {
             value,
             fade_delay_ms,
             animation,
             ..
-        } => render_value(&ValueType::FadeInValue {
-            value: *value,
-            fade_delay_ms: *fade_delay_ms,
-            animation: animation.clone(),
-        }),
-        Cell::FixedValue { value } => render_value(&ValueType::FixedValue(*value)),
-        Cell::Error { value, .. } => render_value(&ValueType::Error(*value)),
+        } => render_value(
+            &ValueType::FadeInValue {
+                value: *value,
+                fade_delay_ms: *fade_delay_ms,
+                animation: animation.clone(),
+            },
+            size,
+        ),
+        Cell::FixedValue { value } => render_value(&ValueType::FixedValue(*value), size),
+        Cell::Error { value, .. } => render_value(&ValueType::Error(*value), size),
     }
 }
 
@@ -189,7 +670,7 @@ enum ValueType {
     Error(u8),
 }
 
-fn render_value(value: &ValueType) -> leptos::HtmlElement<leptos::html::Div> {
+fn render_value(value: &ValueType, size: Size) -> leptos::HtmlElement<leptos::html::Div> {
     let (style, class) = match value {
         ValueType::Value(_) => (
             String::default(),
@@ -221,8 +702,195 @@ fn render_value(value: &ValueType) -> leptos::HtmlElement<leptos::html::Div> {
     view! {
         <div>
             <p class=class style=style>
-                {v}
+                {size.digit_char(v)}
             </p>
         </div>
     }
 }
+
+fn check_puzzle(
+    operation: Operation,
+    givens: Vec<u8>,
+    game_state: RwSignal<GameState>,
+    sudoku: RwSignal<SudokuData>,
+    worker: SolverWorker,
+) {
+    if worker.busy.get_untracked().is_some() {
+        worker.cancel();
+    }
+    let started = worker.run(operation, "CHECK", move |response| {
+        let Outcome::Failed { error, .. } = response.outcome else {
+            return;
+        };
+        if sudoku.with_untracked(|sudoku| sudoku.fixed_digits() == givens) {
+            sudoku.set(SudokuData::default());
+            game_state.update(|state| state.message = Some(error));
+        }
+    });
+    show_if_not_started(game_state, started);
+}
+
+#[component]
+pub fn SamuraiGame() -> impl IntoView {
+    let params = use_query::<SudokuParams>();
+    let samurai = create_rw_signal(Samurai::default());
+    let selected = create_rw_signal(None::<Pos>);
+    let message = create_rw_signal(String::new());
+    let worker = unwrap_or_panic(use_context::<SolverWorker>());
+    let on_key_down = move |ev: KeyboardEvent| {
+        if let Some(pos) = selected() {
+            samurai.update(|samurai| type_into_samurai(samurai, pos, &ev.key()));
+        }
+    };
+    let on_solve = move |_| {
+        let (operation, givens) = samurai
+            .with_untracked(|samurai| (Operation::solve_samurai(samurai), samurai.fixed_digits()));
+        let started = worker.run(operation, "SOLVE", move |response| {
+            if samurai.with_untracked(|samurai| samurai.fixed_digits() != givens) {
+                return;
+            }
+            match response.outcome {
+                Outcome::Solved(solution) => {
+                    samurai.update(|samurai| samurai.fill(&solution));
+                    message.set("Solved".into());
+                }
+                Outcome::Failed { error, .. } => message.set(error),
+                _ => {}
+            }
+        });
+        if let Err(busy) = started {
+            message.set(busy);
+        }
+    };
+    let on_reset = move |_| {
+        samurai.update(|samurai| *samurai = Samurai::with_givens(&samurai.fixed_digits()));
+        message.set(String::new());
+    };
+    let button = "btn-primary p-2 px-4 font-sans font-bold text-white";
+    view! {
+        {move || samurai.set(params.with(unwrap_samurai))}
+        <div class="p-1 h-full min-h-screen w-full bg-sky-100 dark:bg-black fade-dark">
+            <div class="m-10 p-10 space-y-6 bg-slate-300 dark:bg-zinc-950 outline outline-1 outline-slate-300 dark:outline-zinc-900 flex flex-col text-center items-center justify-center shadow-lg rounded-3xl fade-dark">
+                <div
+                    tabindex="0"
+                    on:keydown=on_key_down
+                    style="width: min(80vw, 80vh);height: min(80vw, 80vh);font-family: 'Source Sans Pro', serif;grid-template-columns: repeat(21, 1fr);grid-template-rows: repeat(21, 1fr)"
+                    class="grid m-auto outline-none lining-nums"
+                >
+                    {(0..SIDE)
+                        .flat_map(|row| (0..SIDE).map(move |col| (row, col)))
+                        .map(|(row, col)| {
+                            view! {
+                                <SamuraiCell row=row col=col samurai=samurai selected=selected />
+                            }
+                        })
+                        .collect_view()}
+                </div>
+                <div class="flex space-x-4">
+                    <div class=button on:click=on_solve>
+                        "SOLVE"
+                    </div>
+                    <div class=button on:click=on_reset>
+                        "RESET"
+                    </div>
+                    <a class=button href="/sudoku_solver_wasm/">
+                        "BACK"
+                    </a>
+                </div>
+                <div class="bg-slate-100 dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-3xl p-4 shadow-lg text-xs fade-dark">
+                    <p class="font-mono text-slate-400">
+                        {move || samurai.with(ToString::to_string)}
+                    </p>
+                    <p class="font-mono dark:text-white fade-dark">{message}</p>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+fn type_into_samurai(samurai: &mut Samurai, (row, col): Pos, key: &str) {
+    if matches!(samurai.get(row, col), Some(Cell::FixedValue { .. })) {
+        return;
+    }
+    if let Some(digit) = key.chars().next().and_then(|c| Size::Nine.parse_digit(c)) {
+        samurai.unset(row, col);
+        samurai.set(row, col, digit, false);
+    } else if matches!(key, "Backspace" | "Delete") {
+        samurai.unset(row, col);
+    }
+}
+
+const SAMURAI_BOX_SIDES: [&str; 4] = [
+    "border-t-2 border-t-gray-800",
+    "border-r-2 border-r-gray-800",
+    "border-b-2 border-b-gray-800",
+    "border-l-2 border-l-gray-800",
+];
+
+#[component]
+fn SamuraiCell(
+    row: usize,
+    col: usize,
+    samurai: RwSignal<Samurai>,
+    selected: RwSignal<Option<Pos>>,
+) -> impl IntoView {
+    if grids_at(row, col).is_empty() {
+        return view! { <div /> }.into_view();
+    }
+    let cell = create_memo(move |_| samurai.with(|samurai| samurai.get(row, col)));
+    let edges = [row % 3 == 0, col % 3 == 2, row % 3 == 2, col % 3 == 0];
+    let sides: Vec<_> = edges
+        .iter()
+        .zip(SAMURAI_BOX_SIDES)
+        .filter_map(|(&edge, side)| edge.then_some(side))
+        .collect();
+    let sides = sides.join(" ");
+    let class = move || {
+        let background = if selected() == Some((row, col)) {
+            "bg-cerulean-blue-300 dark:bg-zinc-800"
+        } else {
+            "bg-white dark:bg-black hover:bg-cerulean-blue-100 dark:hover:bg-zinc-900"
+        };
+        format!(
+            "flex items-center justify-center border border-gray-300 dark:border-zinc-700 cursor-pointer {background} {sides}"
+        )
+    };
+    view! {
+        <div
+            style="font-size: min(2.6vw, 2.6vh)"
+            class=class
+            on:mousedown=move |_| selected.set(Some((row, col)))
+        >
+            {move || cell().map(render_samurai_cell)}
+        </div>
+    }
+    .into_view()
+}
+
+fn render_samurai_cell(cell: Cell) -> leptos::View {
+    match cell {
+        Cell::Empty { choices } => view! {
+            <div
+                style="font-size: min(0.8vw, 0.8vh)"
+                class="grid grid-cols-3 w-full h-full leading-none text-slate-500"
+            >
+                {choices[..9]
+                    .iter()
+                    .zip(1..)
+                    .map(|(&choice, digit)| {
+                        view! { <p>{choice.then(|| Size::Nine.digit_char(digit))}</p> }
+                    })
+                    .collect_view()}
+            </div>
+        }
+        .into_view(),
+        Cell::FixedValue { value } => {
+            render_value(&ValueType::FixedValue(value), Size::Nine).into_view()
+        }
+        Cell::Value { value, .. }
+        | Cell::Error { value, .. }
+        | Cell::AnimatedValue { value, .. } => {
+            render_value(&ValueType::Value(value), Size::Nine).into_view()
+        }
+    }
+}
@@ -1,69 +1,111 @@
 use leptos::{
-    component, ev::MouseEvent, update, use_context, view, CollectView, IntoView, RwSignal,
-    SignalUpdate,
+    component, create_effect, create_memo, create_rw_signal, ev::MouseEvent, event_target_value,
+    update, use_context, view, CollectView, IntoView, RwSignal, Signal, SignalGet,
+    SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, SignalWithUntracked,
 };
+use leptos_use::use_interval_fn;
 
-use crate::{
+use sudoku_core::{
     actions::{
-        apply_solution, load_random_sudoku, toggle_choice_if_selected, toggle_digit_if_selected,
+        apply_digit, check_selected_cells, clear_colors_if_selected, reveal_active_cell,
+        toggle_color_if_selected,
+    },
+    auto::{solve_up_to, Level},
+    benchmark::Timing,
+    bindings::{
+        get_color_hotkeys, get_generator_hotkeys, get_solver_hotkeys, CHECK_CELLS_KEY,
+        CLEAR_COLORS_KEY, REVEAL_CELL_KEY,
     },
     generator::Difficulty,
-    hotkeys::{get_generator_hotkeys, get_solver_hotkeys},
-    state::{DigitMode, GameState},
-    sudoku_data::SudokuData,
+    protocol::{Operation, Outcome},
+    puzzle::Puzzle,
+    size::Size,
+    solution_path::SolutionPath,
+    state::{DigitMode, GameState, HighlightKind},
+    sudoku_data::{Color, SudokuData},
+    techniques::Duration,
+    trace::Replay,
+};
+
+use crate::{
+    actions::{
+        apply_solution, apply_technique, give_hint, load_random_sudoku, show_if_not_started,
+    },
     util::unwrap_or_panic,
+    worker::SolverWorker,
 };
 
 #[component]
 pub fn DigitDisplay() -> impl IntoView {
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let size = create_memo(move |_| sudoku_data.with(SudokuData::size));
     view! {
         <div class="p-2 flex flex-col rounded-2xl bg-slate-100 dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 shadow-2xl justify-between fade-dark">
             <DigitModeDisplay />
             <div class="flex flex-col space-y-2">
-                <DigitButtonRow start_digit=1 />
-                <DigitButtonRow start_digit=4 />
-                <DigitButtonRow start_digit=7 />
+                {move || {
+                    let size = size();
+                    (0..size.digits() / size.box_cols())
+                        .map(|row| {
+                            let start_digit = (row * size.box_cols() + 1) as u8;
+                            view! { <DigitButtonRow size=size start_digit=start_digit /> }
+                        })
+                        .collect_view()
+                }}
             </div>
         </div>
     }
 }
 
 #[component]
-fn DigitButtonRow(start_digit: u8) -> impl IntoView {
+fn DigitButtonRow(size: Size, start_digit: u8) -> impl IntoView {
     view! {
         <div class="flex space-x-2 float-right">
-            <DigitButton digit=start_digit />
-            <DigitButton digit=start_digit + 1 />
-            <DigitButton digit=start_digit + 2 />
+            {(start_digit..start_digit + size.box_cols() as u8)
+                .map(|digit| view! { <DigitButton size=size digit=digit /> })
+                .collect_view()}
         </div>
     }
 }
 
 #[component]
-fn DigitButton(digit: u8) -> impl IntoView {
+fn DigitButton(size: Size, digit: u8) -> impl IntoView {
     let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
     let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
     let current_scope = unwrap_or_panic(use_context::<RwSignal<DigitMode>>());
     let on_click = move |_| {
         update!(|game_state, sudoku_data| {
-            match current_scope() {
-                DigitMode::Value => toggle_digit_if_selected(game_state, sudoku_data, digit),
-                DigitMode::Choice => toggle_choice_if_selected(game_state, sudoku_data, digit),
-            }
+            apply_digit(current_scope(), game_state, sudoku_data, digit);
         });
     };
+    // four buttons per row would not fit at full size
+    let (button_class, text_class) = if size.box_cols() > 3 {
+        (
+            "btn-primary w-14 h-14 flex items-center justify-center",
+            "leading-none text-white text-3xl",
+        )
+    } else {
+        (
+            "btn-primary w-20 h-20 flex items-center justify-center",
+            "leading-none text-white text-5xl",
+        )
+    };
     view! {
-        <div class="btn-primary w-20 h-20 flex items-center justify-center" on:click=on_click>
-            <p
-                class="leading-none text-white text-5xl"
-                style="font-family: 'Source Sans Pro', serif"
-            >
-                {digit}
+        <div class=button_class on:click=on_click>
+            <p class=text_class style="font-family: 'Source Sans Pro', serif">
+                {size.digit_char(digit)}
             </p>
         </div>
     }
 }
 
+const SLIDER_OFFSETS: [&str; 4] = [
+    "",
+    "translate-x-full",
+    "translate-x-[200%]",
+    "translate-x-[300%]",
+];
+
 #[component]
 fn DigitModeDisplay() -> impl IntoView {
     let digit_mode = unwrap_or_panic(use_context::<RwSignal<DigitMode>>());
@@ -77,31 +119,31 @@ fn DigitModeDisplay() -> impl IntoView {
             <div class="basis-8">
                 <KeyButton key="↹" />
             </div>
-            <div class="max-w-full h-full flex relative basis-full font-sans font-bold">
+            <div class="max-w-full h-full flex relative basis-full font-sans font-bold text-xs">
                 // this just makes sure that optional tailwind classes are compiled
                 // bg-slate-300 left-10 translate-x-full text-slate-300
-                <div class="w-1/2 inline-flex h-full items-center justify-center">
-                    <p
-                        class:text-slate-300=move || digit_mode() == DigitMode::Choice
-                        class:text-white=move || digit_mode() == DigitMode::Value
-                        class="z-10 transition-all"
-                    >
-                        DIGITS
-                    </p>
-                </div>
-                <div class="w-1/2 inline-flex h-full items-center justify-center">
-                    <p
-                        class:text-slate-300=move || digit_mode() == DigitMode::Value
-                        class:text-white=move || digit_mode() == DigitMode::Choice
-                        class="z-10 transition-all"
-                    >
-                        CHOICES
-                    </p>
-                </div>
-                <div
-                    class:translate-x-full=move || digit_mode() == DigitMode::Choice
-                    class="w-1/2 h-full bg-slate-500 dark:bg-cerulean-blue-500 outline outline-1 outline-slate-500 dark:outline-cerulean-blue-400 opacity-50 absolute transition-all rounded-lg fade-dark"
-                />
+                {DigitMode::ALL
+                    .into_iter()
+                    .map(|mode| {
+                        view! {
+                            <div class="w-1/4 inline-flex h-full items-center justify-center">
+                                <p
+                                    class:text-slate-300=move || digit_mode() != mode
+                                    class:text-white=move || digit_mode() == mode
+                                    class="z-10 transition-all"
+                                >
+                                    {mode.label()}
+                                </p>
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+                <div class=move || {
+                    format!(
+                        "w-1/4 h-full bg-slate-500 dark:bg-cerulean-blue-500 outline outline-1 outline-slate-500 dark:outline-cerulean-blue-400 opacity-50 absolute transition-all rounded-lg fade-dark {}",
+                        SLIDER_OFFSETS[digit_mode().index()],
+                    )
+                } />
             </div>
         </div>
     }
@@ -115,11 +157,37 @@ pub fn SudokuDisplay() -> impl IntoView {
         <div class="bg-slate-100 dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-3xl p-4 shadow-lg text-xs fade-dark">
             <p class="font-mono dark:text-white fade-dark">{move || sudoku_data().to_string()}</p>
             <p class="font-mono text-slate-400">{move || sudoku_data().to_compressed()}</p>
+            <p class="font-mono text-slate-400">
+                {move || sudoku_data.with(|sudoku| Puzzle::from(sudoku).to_string())}
+            </p>
             <Message />
+            <Busy />
         </div>
     }
 }
 
+#[component]
+fn Busy() -> impl IntoView {
+    let worker = unwrap_or_panic(use_context::<SolverWorker>());
+    move || {
+        worker.busy.get().map(|label| {
+            view! {
+                <div class="flex items-center space-x-2 pt-2">
+                    <p class="font-mono dark:text-white fade-dark animate-pulse">
+                        {format!("{label}…")}
+                    </p>
+                    <div
+                        class="btn-primary px-2 py-1 font-sans font-bold text-white"
+                        on:click=move |_| worker.cancel()
+                    >
+                        "CANCEL"
+                    </div>
+                </div>
+            }
+        })
+    }
+}
+
 #[component]
 fn Message() -> impl IntoView {
     let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
@@ -131,6 +199,212 @@ fn Message() -> impl IntoView {
     }
 }
 
+#[component]
+pub fn SolutionPathPanel() -> impl IntoView {
+    let sudoku = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let path = unwrap_or_panic(use_context::<RwSignal<SolutionPath>>());
+    let rewind = move |index: usize| {
+        let mut board = None;
+        path.update(|path| board = path.rewind(index));
+        if let Some(board) = board {
+            sudoku.set(board);
+        }
+    };
+    view! {
+        <div class="flex flex-col space-y-1 p-2 max-h-96 overflow-y-auto text-left bg-slate-100 dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-2xl fade-dark">
+            <p class="font-sans font-bold text-slate-400">"PATH"</p>
+            {move || {
+                path.with(|path| {
+                    path.entries()
+                        .iter()
+                        .enumerate()
+                        .map(|(index, entry)| {
+                            let current = index == path.current();
+                            view! {
+                                <p
+                                    class="font-mono text-xs cursor-pointer dark:text-white fade-dark"
+                                    class:font-bold=current
+                                    class:text-slate-400=!current
+                                    on:click=move |_| rewind(index)
+                                >
+                                    {entry.to_string()}
+                                </p>
+                            }
+                        })
+                        .collect_view()
+                })
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn ReplayControls() -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let sudoku = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let worker = unwrap_or_panic(use_context::<SolverWorker>());
+    let replay = create_rw_signal(None::<Replay>);
+    let playing = create_rw_signal(false);
+    let speed = create_rw_signal(20_u64);
+
+    // a trace is only played back on the puzzle it was recorded on
+    create_effect(move |_| {
+        let stale = sudoku.with(|sudoku| {
+            replay.with_untracked(|replay| replay.as_ref().is_some_and(|r| !r.matches(sudoku)))
+        });
+        if stale {
+            replay.set(None);
+            playing.set(false);
+        }
+    });
+    let step = move || {
+        let mut more = false;
+        replay.update(|replay| {
+            if let Some(replay) = replay {
+                sudoku.update(|sudoku| more = replay.step(sudoku));
+            }
+        });
+        if !more {
+            playing.set(false);
+        }
+    };
+    use_interval_fn(
+        move || {
+            if playing.get_untracked() {
+                step();
+            }
+        },
+        Signal::derive(move || 1000 / speed.get().max(1)),
+    );
+    let record = move |_| {
+        let (operation, givens) =
+            sudoku.with_untracked(|sudoku| (Operation::trace(sudoku), sudoku.fixed_digits()));
+        let started = worker.run(operation, "TRACE", move |response| {
+            if sudoku.with_untracked(|sudoku| sudoku.fixed_digits() != givens) {
+                return;
+            }
+            let trace = match &response.outcome {
+                Outcome::Traced(trace) => Some(trace.clone()),
+                _ => None,
+            };
+            update!(|game_state, sudoku| {
+                game_state.show_result(response.apply(sudoku));
+            });
+            if let Some(trace) = trace {
+                replay.set(Some(Replay::new(trace)));
+                playing.set(true);
+            }
+        });
+        show_if_not_started(game_state, started);
+    };
+    let progress = move || {
+        replay.with(|replay| {
+            replay.as_ref().map_or_else(String::new, |replay| {
+                format!(
+                    "{}/{}",
+                    replay.position().min(replay.step_count()),
+                    replay.step_count()
+                )
+            })
+        })
+    };
+    view! {
+        <div class="flex items-center space-x-2 p-2 bg-slate-100 dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-2xl fade-dark">
+            <ReplayButton text="TRACE" on_click=record />
+            <ReplayButton
+                text="PLAY"
+                on_click=move |_| playing.set(replay.with(|replay| replay.is_some()))
+            />
+            <ReplayButton text="PAUSE" on_click=move |_| playing.set(false) />
+            <ReplayButton
+                text="STEP"
+                on_click=move |_| {
+                    playing.set(false);
+                    step();
+                }
+            />
+            <input
+                type="range"
+                min="1"
+                max="500"
+                prop:value=move || speed.get()
+                on:input=move |ev| {
+                    if let Ok(value) = event_target_value(&ev).parse() {
+                        speed.set(value);
+                    }
+                }
+            />
+            <p class="font-mono text-xs dark:text-white fade-dark">
+                {move || format!("{}/s", speed.get())}
+            </p>
+            <p class="font-mono text-xs dark:text-white fade-dark">{progress}</p>
+        </div>
+    }
+}
+
+#[component]
+fn ReplayButton(text: &'static str, on_click: impl FnMut(MouseEvent) + 'static) -> impl IntoView {
+    view! {
+        <div class="btn-primary pr-4 p-2 flex items-center" on:click=on_click>
+            <p class="min-h-0 leading-none font-sans font-bold text-white">{text}</p>
+        </div>
+    }
+}
+
+#[component]
+pub fn BenchmarkPanel() -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let sudoku = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let worker = unwrap_or_panic(use_context::<SolverWorker>());
+    let timings = create_rw_signal(Vec::<Timing>::new());
+    let on_click = move |_| {
+        let started = worker.run(Operation::Benchmark, "BENCH", move |response| {
+            if let Outcome::Benchmarked(result) = &response.outcome {
+                timings.set(result.clone());
+            }
+            update!(|game_state, sudoku| {
+                game_state.show_result(response.apply(sudoku));
+            });
+        });
+        show_if_not_started(game_state, started);
+    };
+    view! {
+        <div class="flex flex-col space-y-2 p-2 bg-slate-100 dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-2xl fade-dark">
+            <div class="btn-primary pr-4 p-2 flex items-center" on:click=on_click>
+                <p class="min-h-0 leading-none font-sans font-bold text-white">"BENCH"</p>
+            </div>
+            <table class="font-mono text-xs dark:text-white fade-dark">
+                <tr class="text-slate-400">
+                    <th class="pr-4 text-left">"TIMED"</th>
+                    <th class="pr-4 text-right">"MIN"</th>
+                    <th class="pr-4 text-right">"MEDIAN"</th>
+                    <th class="text-right">"MAX"</th>
+                </tr>
+                {move || {
+                    timings
+                        .get()
+                        .into_iter()
+                        .map(|timing| view! { <TimingRow timing=timing /> })
+                        .collect_view()
+                }}
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn TimingRow(timing: Timing) -> impl IntoView {
+    let stats = timing.stats;
+    view! {
+        <tr>
+            <td class="pr-4 text-left">{timing.name}</td>
+            <td class="pr-4 text-right">{Duration(stats.min).to_string()}</td>
+            <td class="pr-4 text-right">{Duration(stats.median).to_string()}</td>
+            <td class="text-right">{Duration(stats.max).to_string()}</td>
+        </tr>
+    }
+}
+
 #[component]
 fn KeyboardShortcut(
     key: &'static str,
@@ -159,10 +433,8 @@ fn KeyButton(key: &'static str) -> impl IntoView {
 pub fn KeyboardShortcuts() -> impl IntoView {
     let set_sudoku = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
     let set_game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
-
-    let with_signals = move |f: fn(&mut SudokuData) -> crate::Result<String>| {
-        apply_solution(set_game_state, set_sudoku, f)
-    };
+    let path = unwrap_or_panic(use_context::<RwSignal<SolutionPath>>());
+    let worker = unwrap_or_panic(use_context::<SolverWorker>());
 
     view! {
         <div class="flex space-y-2 p-2 bg-slate-100 dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-2xl flex-col fade-dark">
@@ -173,11 +445,25 @@ pub fn KeyboardShortcuts() -> impl IntoView {
                         <KeyboardShortcut
                             key=shortcut.key
                             action=shortcut.action
-                            on_click=with_signals(shortcut.on_click)
+                            on_click=apply_solution(set_game_state, set_sudoku, path, shortcut, worker)
                         />
                     }
                 })
                 .collect_view()}
+            <KeyboardShortcut
+                key=REVEAL_CELL_KEY
+                action="REVEAL CELL"
+                on_click=move |_| {
+                    give_hint(set_game_state, set_sudoku, worker, reveal_active_cell);
+                }
+            />
+            <KeyboardShortcut
+                key=CHECK_CELLS_KEY
+                action="CHECK CELLS"
+                on_click=move |_| {
+                    give_hint(set_game_state, set_sudoku, worker, check_selected_cells);
+                }
+            />
         </div>
     }
 }
@@ -208,10 +494,12 @@ fn GenerateSudokuButton(
     text: &'static str,
     difficulty: Difficulty,
 ) -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let worker = unwrap_or_panic(use_context::<SolverWorker>());
     view! {
         <div
             class="btn-primary pr-4 p-2 space-x-2 flex items-center"
-            on:click=move |_| load_random_sudoku(difficulty)
+            on:click=move |_| load_random_sudoku(difficulty, game_state, worker)
         >
             <KeyButton key=key />
             <p class="min-h-0 leading-none font-sans font-bold text-white">{text}</p>
@@ -219,6 +507,127 @@ fn GenerateSudokuButton(
     }
 }
 
+#[component]
+pub fn ColorPalette() -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let on_clear = move |_| {
+        update!(|game_state, sudoku_data| {
+            clear_colors_if_selected(game_state, sudoku_data);
+        });
+    };
+    view! {
+        <div class="flex space-y-2 p-2 bg-slate-100 justify-start dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-2xl flex-col fade-dark">
+            {get_color_hotkeys()
+                .into_iter()
+                .map(|shortcut| view! { <ColorButton key=shortcut.key color=shortcut.color /> })
+                .collect_view()}
+            <div class="btn-primary pr-4 p-2 space-x-2 flex items-center" on:click=on_clear>
+                <KeyButton key=CLEAR_COLORS_KEY />
+                <p class="min-h-0 leading-none font-sans font-bold text-white">CLEAR</p>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ColorButton(key: &'static str, color: Color) -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let sudoku_data = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let on_click = move |_| {
+        update!(|game_state, sudoku_data| {
+            toggle_color_if_selected(game_state, sudoku_data, color);
+        });
+    };
+    view! {
+        <div
+            class="btn-primary pr-4 p-2 space-x-2 flex items-center"
+            class:outline=move || game_state().color == color
+            class:outline-white=move || game_state().color == color
+            on:click=on_click
+        >
+            <KeyButton key=key />
+            <div class=format!("w-6 h-6 rounded-lg {}", color.class()) />
+        </div>
+    }
+}
+
+#[component]
+pub fn HighlightSettings() -> impl IntoView {
+    view! {
+        <div class="flex space-y-2 p-2 bg-slate-100 justify-start dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-2xl flex-col fade-dark">
+            <HighlightToggle kind=HighlightKind::Peers text="PEERS" />
+            <HighlightToggle kind=HighlightKind::SameDigit text="SAME DIGIT" />
+            <HighlightToggle kind=HighlightKind::Candidates text="CANDIDATES" />
+        </div>
+    }
+}
+
+#[component]
+fn HighlightToggle(kind: HighlightKind, text: &'static str) -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let enabled = move || game_state().highlights.get(kind);
+    let on_click = move |_| {
+        update!(|game_state| {
+            game_state.highlights.toggle(kind);
+        });
+    };
+    view! {
+        <div
+            class="btn-primary pr-4 p-2 space-x-2 flex items-center"
+            class:opacity-50=move || !enabled()
+            on:click=on_click
+        >
+            <KeyButton key="✓" />
+            <p class="min-h-0 leading-none font-sans font-bold text-white">{text}</p>
+        </div>
+    }
+}
+
+#[component]
+pub fn LimitedSolve() -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let sudoku = unwrap_or_panic(use_context::<RwSignal<SudokuData>>());
+    let path = unwrap_or_panic(use_context::<RwSignal<SolutionPath>>());
+    let on_click = move |_| {
+        let level = game_state.with_untracked(|state| state.level);
+        let solve = move |sudoku: &mut SudokuData| solve_up_to(sudoku, level);
+        apply_technique(level.label(), solve, game_state, sudoku, path);
+    };
+    view! {
+        <div class="flex space-y-2 p-2 bg-slate-100 justify-start dark:bg-zinc-900 outline outline-1 outline-slate-100 dark:outline-zinc-800 rounded-2xl flex-col fade-dark">
+            {Level::LIMITS
+                .into_iter()
+                .map(|level| view! { <LevelToggle level=level /> })
+                .collect_view()}
+            <div class="btn-primary pr-4 p-2 flex items-center" on:click=on_click>
+                <p class="min-h-0 leading-none font-sans font-bold text-white">"SOLVE"</p>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn LevelToggle(level: Level) -> impl IntoView {
+    let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
+    let picked = move || game_state.with(|state| state.level == level);
+    let on_click = move |_| {
+        update!(|game_state| {
+            game_state.level = level;
+        });
+    };
+    view! {
+        <div
+            class="btn-primary pr-4 p-2 space-x-2 flex items-center"
+            class:opacity-50=move || !picked()
+            on:click=on_click
+        >
+            <KeyButton key="✓" />
+            <p class="min-h-0 leading-none font-sans font-bold text-white">{level.label()}</p>
+        </div>
+    }
+}
+
 #[component]
 pub fn DarkModeToggle() -> impl IntoView {
     let game_state = unwrap_or_panic(use_context::<RwSignal<GameState>>());
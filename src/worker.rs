@@ -0,0 +1,145 @@
+use leptos::leptos_dom::logging::console_error;
+use leptos::{
+    create_rw_signal, provide_context, store_value, RwSignal, SignalGetUntracked, SignalSet,
+    StoredValue,
+};
+use sudoku_core::protocol::{Operation, Request, Response, READY};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{MessageEvent, Worker};
+
+/// Written by trunk next to the app, see the `sudoku_worker` link in
+/// `index.html`.
+const LOADER: &str = "./sudoku_worker_loader.js";
+
+type OnMessage = Closure<dyn Fn(MessageEvent)>;
+
+struct Pending {
+    id: u32,
+    json: String,
+    on_response: Box<dyn FnOnce(Response)>,
+}
+
+#[derive(Clone, Copy)]
+pub struct SolverWorker {
+    worker: StoredValue<Option<(Worker, OnMessage)>>,
+    ready: StoredValue<bool>,
+    pending: StoredValue<Option<Pending>>,
+    next_id: StoredValue<u32>,
+    pub busy: RwSignal<Option<&'static str>>,
+}
+
+impl SolverWorker {
+    fn new() -> Self {
+        let worker = Self {
+            worker: store_value(None),
+            ready: store_value(false),
+            pending: store_value(None),
+            next_id: store_value(0),
+            busy: create_rw_signal(None),
+        };
+        worker.spawn();
+        worker
+    }
+
+    fn spawn(self) {
+        let Ok(worker) = Worker::new(LOADER) else {
+            console_error("Failed to start worker");
+            return;
+        };
+        let on_message = Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+            if let Some(data) = event.data().as_string() {
+                self.receive(&data);
+            }
+        });
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        self.ready.set_value(false);
+        self.worker.set_value(Some((worker, on_message)));
+    }
+
+    fn receive(self, data: &str) {
+        if data == READY {
+            self.ready.set_value(true);
+            self.send_pending();
+            return;
+        }
+        let Ok(response) = serde_json::from_str::<Response>(data) else {
+            console_error("Invalid response from worker");
+            return;
+        };
+        let mut pending = None;
+        self.pending.update_value(|current| {
+            if current.as_ref().is_some_and(|p| p.id == response.id) {
+                pending = current.take();
+            }
+        });
+        // answers to cancelled requests are dropped
+        if let Some(pending) = pending {
+            self.busy.set(None);
+            (pending.on_response)(response);
+        }
+    }
+
+    pub fn run(
+        self,
+        operation: Operation,
+        label: &'static str,
+        on_response: impl FnOnce(Response) + 'static,
+    ) -> Result<(), String> {
+        if let Some(running) = self.busy.get_untracked() {
+            return Err(format!("Still busy with {running}"));
+        }
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id.wrapping_add(1));
+        let Ok(json) = serde_json::to_string(&Request { id, operation }) else {
+            return Err("Failed to encode request".to_string());
+        };
+        self.pending.set_value(Some(Pending {
+            id,
+            json,
+            on_response: Box::new(on_response),
+        }));
+        self.busy.set(Some(label));
+        if self.ready.get_value() {
+            self.send_pending();
+        }
+        Ok(())
+    }
+
+    fn send_pending(self) {
+        let Some(json) = self
+            .pending
+            .with_value(|pending| pending.as_ref().map(|p| p.json.clone()))
+        else {
+            return;
+        };
+        let sent = self.worker.with_value(|worker| {
+            worker
+                .as_ref()
+                .map(|(worker, _)| worker.post_message(&JsValue::from_str(&json)))
+        });
+        if !matches!(sent, Some(Ok(()))) {
+            console_error("Failed to send request to worker");
+            self.pending.set_value(None);
+            self.busy.set(None);
+        }
+    }
+
+    /// A worker cannot be interrupted, so it is terminated and a new one is
+    /// started.
+    pub fn cancel(self) {
+        let mut old = None;
+        self.worker.update_value(|worker| old = worker.take());
+        if let Some((worker, _)) = old {
+            worker.terminate();
+        }
+        self.pending.set_value(None);
+        self.busy.set(None);
+        self.spawn();
+    }
+}
+
+pub fn provide_solver_worker() -> SolverWorker {
+    let worker = SolverWorker::new();
+    provide_context(worker);
+    worker
+}
@@ -0,0 +1,179 @@
+use std::str::FromStr;
+
+use sudoku_core::{
+    backtrack,
+    encoding::decompress_string,
+    generator::{self, Difficulty},
+    puzzle::Puzzle,
+    rating,
+    samurai::Samurai,
+    size::Size,
+    symmetry,
+    techniques::find_solution,
+    variant::Variant,
+    Error, Result,
+};
+
+use crate::output::Outcome;
+
+#[derive(Debug)]
+pub enum Board {
+    Single(Puzzle),
+    Samurai(Samurai),
+}
+
+impl FromStr for Board {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_plain(s).or_else(|err| {
+            decompress_string(s)
+                .ok_or(err)
+                .and_then(|s| parse_plain(&s))
+        })
+    }
+}
+
+fn parse_plain(s: &str) -> Result<Board> {
+    Puzzle::from_str(s)
+        .map(Board::Single)
+        .or_else(|err| Samurai::from_str(s).map(Board::Samurai).or(Err(err)))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Task {
+    Solve,
+    Count(usize),
+    Rate,
+    Canonicalize,
+    Transmute,
+}
+
+impl Task {
+    pub fn apply(self, board: &Board) -> Result<Outcome> {
+        match self {
+            Self::Solve => solve(board).map(Outcome::Solution),
+            Self::Count(limit) => count(board, limit).map(Outcome::Solutions),
+            Self::Rate => rate(board).map(Outcome::Rating),
+            Self::Canonicalize => canonicalize(board).map(Outcome::Canonical),
+            Self::Transmute => transmute(board).map(Outcome::Transmuted),
+        }
+    }
+}
+
+fn solve(board: &Board) -> Result<String> {
+    match board {
+        Board::Single(puzzle) => find_solution(&puzzle.givens, &puzzle.variant)
+            .map(|solution| puzzle.variant.size.format(&solution)),
+        Board::Samurai(samurai) => samurai
+            .solution()
+            .map(|solution| Size::Nine.format(&solution)),
+    }
+}
+
+fn count(board: &Board, limit: usize) -> Result<usize> {
+    let puzzle = single(board)?;
+    Ok(backtrack::find_solutions(&puzzle.givens, &puzzle.variant, limit).len())
+}
+
+fn rate(board: &Board) -> Result<String> {
+    let puzzle = single(board)?;
+    rating::rate(&puzzle.givens, &puzzle.variant).map(|rating| rating.to_string())
+}
+
+fn canonicalize(board: &Board) -> Result<String> {
+    let puzzle = classic(board)?;
+    Ok(Size::Nine.format(&symmetry::canonicalize(&puzzle.givens)))
+}
+
+fn transmute(board: &Board) -> Result<String> {
+    let mut arr = classic(board)?.to_string().into_bytes();
+    generator::transmute(&mut arr);
+    Ok(String::from_utf8(arr)?)
+}
+
+pub fn generate(difficulty: Difficulty) -> Result<String> {
+    let (givens, variant) = generator::get_random_sudoku(difficulty)?;
+    if variant == Variant::default() {
+        Ok(givens)
+    } else {
+        Ok(format!("{givens}:{variant}"))
+    }
+}
+
+fn single(board: &Board) -> Result<&Puzzle> {
+    match board {
+        Board::Single(puzzle) => Ok(puzzle),
+        Board::Samurai(_) => Err(Error::UnsupportedSize),
+    }
+}
+
+fn classic(board: &Board) -> Result<&Puzzle> {
+    let puzzle = single(board)?;
+    if puzzle.variant.is_classic() {
+        Ok(puzzle)
+    } else {
+        Err(Error::UnsupportedVariant)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use sudoku_core::{
+        encoding::compress_string,
+        generator::{HARD_SUDOKUS, SAMURAI_SUDOKUS},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_parse_board() -> Result<()> {
+        assert!(matches!(
+            Board::from_str(HARD_SUDOKUS[0])?,
+            Board::Single(_)
+        ));
+        let compressed =
+            compress_string(&format!("{}:x", HARD_SUDOKUS[0])).ok_or(Error::ParsePuzzle)?;
+        let Board::Single(puzzle) = Board::from_str(&compressed)? else {
+            return Err(Error::ParsePuzzle);
+        };
+        assert!(puzzle.variant.diagonal);
+        assert!(matches!(
+            Board::from_str(SAMURAI_SUDOKUS[0])?,
+            Board::Samurai(_)
+        ));
+        assert!(Board::from_str("12345").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_classic_only() -> Result<()> {
+        let diagonal = Board::from_str(&format!("{}:x", HARD_SUDOKUS[0]))?;
+        assert!(matches!(
+            Task::Transmute.apply(&diagonal),
+            Err(Error::UnsupportedVariant)
+        ));
+        let samurai = Board::from_str(SAMURAI_SUDOKUS[0])?;
+        assert!(matches!(
+            Task::Rate.apply(&samurai),
+            Err(Error::UnsupportedSize)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_solutions() -> Result<()> {
+        let board = Board::from_str(HARD_SUDOKUS[0])?;
+        assert!(matches!(
+            Task::Count(10).apply(&board)?,
+            Outcome::Solutions(1)
+        ));
+        let empty = Board::from_str(&format!("{}:s4", ".".repeat(16)))?;
+        assert!(matches!(
+            Task::Count(10).apply(&empty)?,
+            Outcome::Solutions(10)
+        ));
+        Ok(())
+    }
+}
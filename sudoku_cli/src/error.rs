@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter};
+
+use derive_more::From;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, From)]
+pub enum Error {
+    #[from]
+    Io(std::io::Error),
+    #[from]
+    Json(serde_json::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "Failed to write JSON: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,243 @@
+#![warn(
+    // missing_docs,
+    // unreachable_pub,
+    keyword_idents,
+    unexpected_cfgs,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    unused_crate_dependencies,
+    unused_extern_crates,
+    unused_import_braces,
+    future_incompatible,
+    nonstandard_style,
+    bad_style,
+    dead_code,
+    improper_ctypes,
+    non_shorthand_field_patterns,
+    no_mangle_generic_items,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true,
+)]
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use commands::{Board, Task};
+use error::Result;
+use output::{Format, Outcome, Record};
+use sudoku_core::generator::Difficulty;
+use sudoku_core::size::Size;
+
+mod commands;
+mod error;
+mod output;
+
+/// Solve, rate and generate sudokus in bulk. Puzzles are read one per line,
+/// written like the `?puzzle=` links of the web app: the digits of every
+/// cell with `.` for empty ones, optionally followed by `:` and the rules.
+#[derive(Debug, Parser)]
+#[command(name = "sudoku", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// How results are written
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+    /// Compress the boards that are written, like the links of the web app
+    #[arg(long, global = true)]
+    compressed: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve every puzzle
+    Solve(Input),
+    /// Count the solutions of every puzzle
+    Count {
+        #[command(flatten)]
+        input: Input,
+        /// Stop counting at this many solutions
+        #[arg(long, default_value_t = 1_000)]
+        limit: usize,
+    },
+    /// Rate every puzzle by the hardest technique it needs
+    Rate(Input),
+    /// Write every classic puzzle in a canonical form, which is the same for
+    /// all puzzles that only differ by swapped lines and digits
+    Canonicalize(Input),
+    /// Swap lines and digits of every classic puzzle at random
+    Transmute(Input),
+    /// Generate new puzzles
+    Generate {
+        #[arg(long, value_enum, default_value_t = Level::Hard)]
+        difficulty: Level,
+        /// How many puzzles to generate
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Input {
+    /// Files with one puzzle per line. Standard input is read if none are
+    /// given. Blank lines and lines starting with `#` are skipped.
+    files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Level {
+    Hard,
+    Clue17,
+    Extreme,
+    Diagonal,
+    #[value(name = "4x4")]
+    Four,
+    #[value(name = "6x6")]
+    Six,
+    #[value(name = "9x9")]
+    Nine,
+    #[value(name = "16x16")]
+    Sixteen,
+    Jigsaw,
+    Samurai,
+}
+
+impl From<Level> for Difficulty {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Hard => Self::Hard,
+            Level::Clue17 => Self::Clue17,
+            Level::Extreme => Self::Extreme,
+            Level::Diagonal => Self::Diagonal,
+            Level::Four => Self::Sized(Size::Four),
+            Level::Six => Self::Sized(Size::Six),
+            Level::Nine => Self::Sized(Size::Nine),
+            Level::Sixteen => Self::Sized(Size::Sixteen),
+            Level::Jigsaw => Self::Jigsaw,
+            Level::Samurai => Self::Samurai,
+        }
+    }
+}
+
+type Records<'a> = Box<dyn Iterator<Item = Result<Record>> + 'a>;
+
+impl Cli {
+    fn records(&self) -> Result<Records<'_>> {
+        let (input, task) = match &self.command {
+            Command::Solve(input) => (input, Task::Solve),
+            Command::Count { input, limit } => (input, Task::Count(*limit)),
+            Command::Rate(input) => (input, Task::Rate),
+            Command::Canonicalize(input) => (input, Task::Canonicalize),
+            Command::Transmute(input) => (input, Task::Transmute),
+            Command::Generate { difficulty, count } => {
+                let difficulty = Difficulty::from(*difficulty);
+                return Ok(Box::new(
+                    (0..*count).map(move |_| Ok(self.generate(difficulty))),
+                ));
+            }
+        };
+        let lines = read_lines(&input.files)?;
+        Ok(Box::new(lines.map(move |line| -> Result<Record> {
+            Ok(self.process(task, &line?))
+        })))
+    }
+
+    fn process(&self, task: Task, line: &str) -> Record {
+        let outcome = Board::from_str(line)
+            .and_then(|board| task.apply(&board))
+            .map(|outcome| self.encode(outcome))
+            .unwrap_or_else(|err| Outcome::Error(err.to_string()));
+        Record {
+            puzzle: line.to_owned(),
+            outcome: Some(outcome),
+        }
+    }
+
+    fn generate(&self, difficulty: Difficulty) -> Record {
+        match commands::generate(difficulty) {
+            Ok(puzzle) if self.compressed => Record {
+                puzzle: output::compress(&puzzle),
+                outcome: None,
+            },
+            Ok(puzzle) => Record {
+                puzzle,
+                outcome: None,
+            },
+            Err(err) => Record {
+                puzzle: String::new(),
+                outcome: Some(Outcome::Error(err.to_string())),
+            },
+        }
+    }
+
+    fn encode(&self, outcome: Outcome) -> Outcome {
+        if self.compressed {
+            outcome.compressed()
+        } else {
+            outcome
+        }
+    }
+}
+
+fn read_lines(files: &[PathBuf]) -> Result<Box<dyn Iterator<Item = io::Result<String>>>> {
+    let lines: Box<dyn Iterator<Item = io::Result<String>>> = if files.is_empty() {
+        Box::new(io::stdin().lines())
+    } else {
+        let readers = files
+            .iter()
+            .map(File::open)
+            .collect::<io::Result<Vec<_>>>()?;
+        Box::new(
+            readers
+                .into_iter()
+                .flat_map(|file| BufReader::new(file).lines()),
+        )
+    };
+    Ok(Box::new(lines.filter_map(|line| match line {
+        Ok(line) if is_skipped(&line) => None,
+        Ok(line) => Some(Ok(line.trim().to_owned())),
+        Err(err) => Some(Err(err)),
+    })))
+}
+
+fn is_skipped(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+fn run(cli: &Cli) -> Result<bool> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut succeeded = true;
+    for record in cli.records()? {
+        let record = record?;
+        succeeded &= !record.is_error();
+        record.write(&mut out, cli.format)?;
+    }
+    out.flush()?;
+    Ok(succeeded)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            let _ = writeln!(io::stderr(), "sudoku: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use sudoku_core::encoding::compress_string;
+
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// One result per line
+    Text,
+    /// One JSON object per line, holding the puzzle and its result
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub puzzle: String,
+    #[serde(flatten)]
+    pub outcome: Option<Outcome>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Solution(String),
+    Solutions(usize),
+    Rating(String),
+    Canonical(String),
+    Transmuted(String),
+    Error(String),
+}
+
+impl Outcome {
+    pub fn compressed(self) -> Self {
+        match self {
+            Self::Solution(s) => Self::Solution(compress(&s)),
+            Self::Canonical(s) => Self::Canonical(compress(&s)),
+            Self::Transmuted(s) => Self::Transmuted(compress(&s)),
+            outcome @ (Self::Solutions(_) | Self::Rating(_) | Self::Error(_)) => outcome,
+        }
+    }
+}
+
+pub fn compress(s: &str) -> String {
+    compress_string(s).unwrap_or_default()
+}
+
+impl Record {
+    pub const fn is_error(&self) -> bool {
+        matches!(self.outcome, Some(Outcome::Error(_)))
+    }
+
+    fn text(&self) -> String {
+        match &self.outcome {
+            None => self.puzzle.clone(),
+            Some(
+                Outcome::Solution(s)
+                | Outcome::Rating(s)
+                | Outcome::Canonical(s)
+                | Outcome::Transmuted(s),
+            ) => s.clone(),
+            Some(Outcome::Solutions(count)) => count.to_string(),
+            Some(Outcome::Error(e)) => format!("error: {e}"),
+        }
+    }
+
+    pub fn write(&self, out: &mut impl Write, format: Format) -> Result<()> {
+        match format {
+            Format::Text => writeln!(out, "{}", self.text())?,
+            Format::Json => {
+                serde_json::to_writer(&mut *out, self)?;
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+}
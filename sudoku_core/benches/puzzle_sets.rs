@@ -0,0 +1,52 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use sudoku_core::{
+    benchmark::{load, PUZZLE_SETS},
+    bindings::get_solver_hotkeys,
+    generator::HARD_SUDOKUS,
+    size::Size,
+    techniques::find_solution,
+    variant::Variant,
+};
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve");
+    for (name, puzzles) in PUZZLE_SETS {
+        let boards: Vec<_> = puzzles
+            .iter()
+            .filter_map(|puzzle| Size::Nine.parse(puzzle))
+            .collect();
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for givens in &boards {
+                    let _ = black_box(find_solution(givens, &Variant::default()));
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn techniques(c: &mut Criterion) {
+    let mut group = c.benchmark_group("techniques");
+    let boards: Vec<_> = HARD_SUDOKUS
+        .iter()
+        .filter_map(|puzzle| load(puzzle).ok())
+        .collect();
+    for hotkey in get_solver_hotkeys() {
+        group.bench_function(hotkey.action, |b| {
+            b.iter_batched(
+                || boards.clone(),
+                |mut boards| {
+                    for sudoku in &mut boards {
+                        let _ = black_box((hotkey.on_click)(sudoku));
+                    }
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, solve, techniques);
+criterion_main!(benches);
@@ -0,0 +1,261 @@
+use crate::size::MAX_DIGITS;
+use crate::state::{DigitMode, GameState};
+use crate::sudoku_data::{Cell, Color, PencilMark, SudokuData};
+use crate::techniques::mark_cell;
+
+pub fn apply_digit(mode: DigitMode, game_state: &GameState, sudoku: &mut SudokuData, digit: u8) {
+    match mode {
+        DigitMode::Value => toggle_digit_if_selected(game_state, sudoku, digit),
+        DigitMode::Choice => toggle_choice_if_selected(game_state, sudoku, digit),
+        DigitMode::Corner => {
+            toggle_pencil_mark_if_selected(game_state, sudoku, PencilMark::Corner, digit);
+        }
+        DigitMode::Centre => {
+            toggle_pencil_mark_if_selected(game_state, sudoku, PencilMark::Centre, digit);
+        }
+    }
+}
+
+pub fn toggle_digit_if_selected(game_state: &GameState, sudoku: &mut SudokuData, digit: u8) {
+    let cells = game_state.selected_cells();
+    let clear = cells.iter().all(|&(row, col)| {
+        let cell = sudoku.get(row, col);
+        matches!(cell, Cell::FixedValue { .. }) || cell.value() == Some(digit)
+    });
+    for (row, col) in cells {
+        if clear || sudoku.get(row, col).value() != Some(digit) {
+            toggle_digit(sudoku, row, col, digit);
+        }
+    }
+}
+
+fn toggle_digit(sudoku: &mut SudokuData, row: usize, col: usize, digit: u8) {
+    let cell = sudoku.get(row, col);
+    match cell {
+        Cell::Empty { choices } => {
+            if choices[(digit - 1) as usize] {
+                sudoku.set(row, col, digit, false);
+            }
+        }
+        Cell::Value { value, choices }
+        | Cell::Error { value, choices }
+        | Cell::AnimatedValue { value, choices, .. } => {
+            toggle_if_available(value, digit, &choices, sudoku, row, col);
+        }
+        Cell::FixedValue { .. } => {}
+    }
+}
+
+pub fn toggle_choice_if_selected(game_state: &GameState, sudoku: &mut SudokuData, digit: u8) {
+    let cells = game_state.selected_cells();
+    let remove = cells.iter().all(|&(row, col)| match sudoku.get(row, col) {
+        Cell::Empty { choices } => choices[(digit - 1) as usize],
+        Cell::Value { .. }
+        | Cell::Error { .. }
+        | Cell::FixedValue { .. }
+        | Cell::AnimatedValue { .. } => true,
+    });
+    for (row, col) in cells {
+        sudoku.set_choice(row, col, digit, !remove);
+    }
+}
+
+pub fn toggle_pencil_mark_if_selected(
+    game_state: &GameState,
+    sudoku: &mut SudokuData,
+    layer: PencilMark,
+    digit: u8,
+) {
+    let cells: Vec<_> = game_state
+        .selected_cells()
+        .into_iter()
+        .filter(|&(row, col)| sudoku.get(row, col).is_empty())
+        .collect();
+    let remove = cells
+        .iter()
+        .all(|&(row, col)| sudoku.marks(row, col).pencil_marks(layer)[(digit - 1) as usize]);
+    for (row, col) in cells {
+        sudoku.set_pencil_mark(row, col, layer, digit, !remove);
+    }
+}
+
+pub fn toggle_color_if_selected(game_state: &mut GameState, sudoku: &mut SudokuData, color: Color) {
+    game_state.color = color;
+    let cells = game_state.selected_cells();
+    let remove = cells
+        .iter()
+        .all(|&(row, col)| sudoku.marks(row, col).color == Some(color));
+    for (row, col) in cells {
+        sudoku.set_color(row, col, if remove { None } else { Some(color) });
+    }
+}
+
+pub fn clear_colors_if_selected(game_state: &GameState, sudoku: &mut SudokuData) {
+    for (row, col) in game_state.selected_cells() {
+        sudoku.set_color(row, col, None);
+        for digit in 1..=sudoku.size().digits() as u8 {
+            sudoku.set_choice_color(row, col, digit, None);
+        }
+    }
+}
+
+pub fn toggle_choice_color(
+    game_state: &GameState,
+    sudoku: &mut SudokuData,
+    (row, col): (usize, usize),
+    digit: u8,
+) {
+    let current = sudoku.marks(row, col).choice_colors[(digit - 1) as usize];
+    let color = Some(game_state.color).filter(|&c| current != Some(c));
+    sudoku.set_choice_color(row, col, digit, color);
+}
+
+fn toggle_if_available(
+    value: u8,
+    digit: u8,
+    choices: &[bool; MAX_DIGITS],
+    sudoku: &mut SudokuData,
+    row: usize,
+    col: usize,
+) {
+    if value == digit {
+        sudoku.unset(row, col);
+    } else {
+        let is_available = choices[(digit - 1) as usize];
+        sudoku.unset(row, col);
+        if is_available {
+            sudoku.set(row, col, digit, false);
+        }
+    }
+}
+
+pub fn clear_digit_if_selected(game_state: &GameState, sudoku: &mut SudokuData) {
+    for (row, col) in game_state.selected_cells() {
+        sudoku.unset(row, col);
+    }
+}
+
+pub fn reveal_active_cell(
+    game_state: &mut GameState,
+    sudoku: &mut SudokuData,
+    solution: &[u8],
+) -> String {
+    let Some((row, col)) = game_state.active_cell else {
+        return "Select a cell to reveal".to_string();
+    };
+    let cell = sudoku.get(row, col);
+    if matches!(cell, Cell::FixedValue { .. }) {
+        return "Givens are already revealed".to_string();
+    }
+    let digit = solution[row * sudoku.size().digits() + col];
+    let name = format!(
+        "r{}c{} is {}",
+        row + 1,
+        col + 1,
+        sudoku.size().digit_char(digit)
+    );
+    if cell.value() == Some(digit) {
+        return format!("{name} already");
+    }
+    sudoku.unset(row, col);
+    sudoku.set(row, col, digit, false);
+    game_state.tally.hints += 1;
+    format!("{name} ({})", game_state.tally)
+}
+
+pub fn check_selected_cells(
+    game_state: &mut GameState,
+    sudoku: &mut SudokuData,
+    solution: &[u8],
+) -> String {
+    let cells: Vec<_> = game_state
+        .selected_cells()
+        .into_iter()
+        .filter(|&(row, col)| {
+            let cell = sudoku.get(row, col);
+            !matches!(cell, Cell::FixedValue { .. }) && cell.value().is_some()
+        })
+        .collect();
+    if cells.is_empty() {
+        return "Select the digits to check".to_string();
+    }
+    let n = sudoku.size().digits();
+    let new_mistakes = cells
+        .iter()
+        .filter(|&&(row, col)| mark_cell(sudoku, row, col, solution[row * n + col]))
+        .count();
+    let wrong = cells
+        .iter()
+        .filter(|&&(row, col)| matches!(sudoku.get(row, col), Cell::Error { .. }))
+        .count();
+    game_state.tally.hints += 1;
+    game_state.tally.mistakes += new_mistakes;
+    format!(
+        "{wrong} of {} checked digits wrong ({})",
+        cells.len(),
+        game_state.tally
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use crate::benchmark::load;
+    use crate::state::Tally;
+    use crate::techniques::find_solution;
+    use crate::test_support::EASY_SUDOKU;
+    use crate::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_reveal_and_check() -> Result<()> {
+        let mut sudoku = load(EASY_SUDOKU)?;
+        let solution = find_solution(&sudoku.fixed_digits(), &sudoku.variant)?;
+        let mut state = GameState::default();
+
+        state.select((0, 0));
+        reveal_active_cell(&mut state, &mut sudoku, &solution);
+        assert_eq!(sudoku.get(0, 0).value(), Some(solution[0]));
+        // revealing it again is not another hint
+        reveal_active_cell(&mut state, &mut sudoku, &solution);
+        assert_eq!(state.tally.hints, 1);
+
+        // a wrong digit next to it, the given in between is not checked
+        let wrong = solution[3] % 9 + 1;
+        sudoku.set(0, 3, wrong, false);
+        state.selection = vec![(0, 0), (0, 2), (0, 3)];
+        let message = check_selected_cells(&mut state, &mut sudoku, &solution);
+        assert!(message.starts_with("1 of 2 checked digits wrong"));
+        assert!(matches!(sudoku.get(0, 3), Cell::Error { .. }));
+        assert_eq!(
+            state.tally,
+            Tally {
+                hints: 2,
+                mistakes: 1
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_twice() -> Result<()> {
+        let mut sudoku = load(EASY_SUDOKU)?;
+        let solution = find_solution(&sudoku.fixed_digits(), &sudoku.variant)?;
+        let mut state = GameState::default();
+
+        sudoku.set(0, 0, solution[0] % 9 + 1, false);
+        state.select((0, 0));
+        check_selected_cells(&mut state, &mut sudoku, &solution);
+        let message = check_selected_cells(&mut state, &mut sudoku, &solution);
+        assert!(message.starts_with("1 of 1 checked digits wrong"));
+        assert_eq!(
+            state.tally,
+            Tally {
+                hints: 2,
+                mistakes: 1
+            }
+        );
+        Ok(())
+    }
+}
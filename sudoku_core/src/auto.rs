@@ -0,0 +1,256 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+use crate::{
+    bindings::{get_technique_hotkeys, Hotkey},
+    diff::Changes,
+    rating::Rating,
+    size::MAX_DIGITS,
+    sudoku_data::{Cell, SudokuData},
+    techniques, Result,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Level {
+    #[default]
+    Singles,
+    Pairs,
+    Triples,
+    Fish,
+    All,
+}
+
+impl Level {
+    pub const LIMITS: [Self; 4] = [Self::Singles, Self::Pairs, Self::Triples, Self::Fish];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Singles => "SINGLES ONLY",
+            Self::Pairs => "UP TO PAIRS",
+            Self::Triples => "UP TO TRIPLES",
+            Self::Fish => "UP TO FISH",
+            Self::All => "ALL TECHNIQUES",
+        }
+    }
+
+    fn allows(self, hotkey: &Hotkey) -> bool {
+        let hardest = match self {
+            Self::Singles => Rating::HiddenSingles,
+            Self::Pairs => Rating::Doubles,
+            Self::Triples => Rating::Triples,
+            Self::Fish => Rating::Fish,
+            Self::All => Rating::Backtracking,
+        };
+        Rating::of(hotkey.action) <= hardest
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Singles => "singles",
+            Self::Pairs => "pairs",
+            Self::Triples => "triples",
+            Self::Fish => "fish",
+            Self::All => "every technique",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoReport {
+    pub counts: Vec<(&'static str, usize)>,
+    pub solved: bool,
+    pub empty: usize,
+    pub level: Level,
+}
+
+/// # Errors
+///
+/// Fails like the techniques, e.g. on boards they do not apply to.
+pub fn apply_all_techniques(sudoku: &mut SudokuData) -> Result<AutoReport> {
+    apply_up_to(sudoku, Level::All)
+}
+
+/// # Errors
+///
+/// Fails like [`apply_all_techniques`].
+pub fn apply_up_to(sudoku: &mut SudokuData, level: Level) -> Result<AutoReport> {
+    let mut techniques: Vec<_> = get_technique_hotkeys()
+        .into_iter()
+        .filter(|hotkey| level.allows(hotkey))
+        .collect();
+    techniques.sort_by_key(|hotkey| Rating::of(hotkey.action));
+    let mut counts: Vec<_> = techniques.iter().map(|hotkey| (hotkey.action, 0)).collect();
+    let mut seen = vec![board_state(sudoku)];
+    while let Some(used) = apply_cheapest(&techniques, sudoku)? {
+        let state = board_state(sudoku);
+        if seen.contains(&state) {
+            break;
+        }
+        seen.push(state);
+        counts[used].1 += 1;
+    }
+    let empty = sudoku.digits().iter().filter(|&&digit| digit == 0).count();
+    Ok(AutoReport {
+        counts,
+        solved: empty == 0,
+        empty,
+        level,
+    })
+}
+
+fn apply_cheapest(techniques: &[Hotkey], sudoku: &mut SudokuData) -> Result<Option<usize>> {
+    for (i, hotkey) in techniques.iter().enumerate() {
+        let before = sudoku.clone();
+        (hotkey.on_click)(sudoku)?;
+        if !Changes::between(&before, sudoku).is_empty() {
+            return Ok(Some(i));
+        }
+    }
+    Ok(None)
+}
+
+fn board_state(sudoku: &SudokuData) -> Vec<(Option<u8>, [bool; MAX_DIGITS])> {
+    sudoku
+        .rows
+        .iter()
+        .flat_map(|row| &row.cells)
+        .map(|cell| match cell {
+            Cell::Empty { choices } => (None, *choices),
+            cell => (cell.value(), [false; MAX_DIGITS]),
+        })
+        .collect()
+}
+
+/// # Errors
+///
+/// Fails like [`apply_all_techniques`].
+pub fn apply_until_stuck(sudoku: &mut SudokuData) -> Result<String> {
+    solve_up_to(sudoku, Level::All)
+}
+
+/// # Errors
+///
+/// Fails like [`apply_all_techniques`].
+pub fn solve_up_to(sudoku: &mut SudokuData, level: Level) -> Result<String> {
+    let now = Instant::now();
+    let report = apply_up_to(sudoku, level)?;
+    let elapsed = techniques::Duration(now.elapsed());
+    Ok(format!("{report} in {elapsed}"))
+}
+
+impl Display for AutoReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.solved {
+            write!(f, "Solved logically")?;
+        } else if self.level == Level::All {
+            write!(f, "Stuck with {} cells empty, needs guessing", self.empty)?;
+        } else {
+            write!(
+                f,
+                "Stuck with {} cells empty, needs more than {}",
+                self.empty, self.level
+            )?;
+        }
+        let used: Vec<_> = self
+            .counts
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(technique, count)| format!("{technique} {count}"))
+            .collect();
+        if !used.is_empty() {
+            write!(f, " with {}", used.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use crate::{
+        benchmark::load,
+        generator::EXTREME_SUDOKUS,
+        test_support::{EASY_SUDOKU, FISH_SUDOKU, PAIRS_SUDOKU},
+        Error,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_solves_easy_sudoku() -> Result<()> {
+        let mut sudoku = load(EASY_SUDOKU)?;
+        let report = apply_all_techniques(&mut sudoku)?;
+        assert!(report.solved);
+        assert_eq!(report.empty, 0);
+        assert!(report.counts.iter().any(|&(_, count)| count > 0));
+        assert!(report.to_string().starts_with("Solved logically with "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reports_when_stuck() -> Result<()> {
+        // at least one of the extreme puzzles is out of reach of the
+        // techniques
+        let mut stuck = None;
+        for givens in EXTREME_SUDOKUS {
+            let report = apply_all_techniques(&mut load(givens)?)?;
+            if !report.solved {
+                stuck = Some(report);
+                break;
+            }
+        }
+        let report = stuck.ok_or(Error::NoSolution)?;
+        assert!(report.empty > 0);
+        assert!(report.to_string().contains("needs guessing"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pairs_help_the_singles() -> Result<()> {
+        let singles = apply_up_to(&mut load(PAIRS_SUDOKU)?, Level::Singles)?;
+        assert!(!singles.solved);
+
+        let report = apply_up_to(&mut load(PAIRS_SUDOKU)?, Level::Pairs)?;
+        assert!(report.solved);
+        let count = |technique| {
+            report
+                .counts
+                .iter()
+                .find(|&&(name, _)| name == technique)
+                .map(|&(_, count)| count)
+        };
+        // a stuck board used to count DOUBLES again on every round
+        assert!(count("DOUBLES").is_some_and(|doubles| (1..=3).contains(&doubles)));
+        assert_eq!(count("TRIPLES"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fish_gets_further_than_triples() -> Result<()> {
+        let triples = apply_up_to(&mut load(FISH_SUDOKU)?, Level::Triples)?;
+        assert!(!triples.solved);
+        let fish = apply_up_to(&mut load(FISH_SUDOKU)?, Level::Fish)?;
+        assert!(fish.solved);
+        assert!(fish.to_string().contains("X-WING"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_limited_to_singles() -> Result<()> {
+        let mut sudoku = load(EXTREME_SUDOKUS[0])?;
+        let report = apply_up_to(&mut sudoku, Level::Singles)?;
+        let techniques: Vec<_> = report.counts.iter().map(|&(name, _)| name).collect();
+        assert_eq!(techniques, ["SINGLES", "CONSTRAINTS", "HIDDEN"]);
+        assert!(!report.solved);
+        assert!(report
+            .to_string()
+            .contains("cells empty, needs more than singles"));
+        Ok(())
+    }
+}
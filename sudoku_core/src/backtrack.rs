@@ -0,0 +1,304 @@
+use crate::{
+    constraint::Constraint,
+    size::Size,
+    trace::{Step, Trace},
+    variant::Variant,
+    Error, Result,
+};
+
+struct Solver {
+    size: Size,
+    digits: Vec<u8>,
+    peers: Vec<Vec<usize>>,
+    constraints: Vec<Box<dyn Constraint>>,
+    budget: usize,
+    steps: Option<Vec<Step>>,
+    max_steps: usize,
+}
+
+impl Solver {
+    fn new(givens: &[u8], variant: &Variant, budget: usize) -> Self {
+        let n = variant.size.digits();
+        let peers = variant
+            .peer_table()
+            .iter()
+            .map(|peers| peers.iter().map(|(row, col)| row * n + col).collect())
+            .collect();
+        Self {
+            size: variant.size,
+            digits: givens.to_vec(),
+            peers,
+            constraints: variant.constraints(),
+            budget,
+            steps: None,
+            max_steps: usize::MAX,
+        }
+    }
+
+    fn record(&mut self, step: Step) {
+        let Some(steps) = &mut self.steps else {
+            return;
+        };
+        if steps.len() < self.max_steps {
+            steps.push(step);
+        } else {
+            self.budget = 0;
+        }
+    }
+
+    fn is_consistent(&self) -> bool {
+        self.digits.iter().zip(&self.peers).all(|(&digit, peers)| {
+            digit == 0 || peers.iter().all(|&peer| self.digits[peer] != digit)
+        })
+    }
+
+    fn candidates(&self) -> Vec<u32> {
+        let all = ((1 << self.size.digits()) - 1) << 1;
+        let mut candidates: Vec<u32> = (0..self.digits.len())
+            .map(|idx| {
+                if self.digits[idx] != 0 {
+                    return 0;
+                }
+                self.peers[idx]
+                    .iter()
+                    .fold(all, |mask, &peer| mask & !(1 << self.digits[peer]))
+            })
+            .collect();
+        for constraint in &self.constraints {
+            constraint.prune(self.size, &self.digits, &mut candidates);
+        }
+        candidates
+    }
+
+    fn search(&mut self, limit: usize, solutions: &mut Vec<Vec<u8>>) {
+        let candidates = self.candidates();
+        let next = (0..self.digits.len())
+            .filter(|&idx| self.digits[idx] == 0)
+            .min_by_key(|&idx| candidates[idx].count_ones());
+        let Some(idx) = next else {
+            solutions.push(self.digits.clone());
+            return;
+        };
+        for digit in 1..=self.size.digits() as u8 {
+            if solutions.len() >= limit || self.budget == 0 {
+                break;
+            }
+            if candidates[idx] & (1 << digit) != 0 {
+                self.budget -= 1;
+                self.digits[idx] = digit;
+                self.record(Step::Place { cell: idx, digit });
+                let found = solutions.len();
+                self.search(limit, solutions);
+                if solutions.len() == found {
+                    self.record(Step::Eliminate { cell: idx, digit });
+                }
+            }
+        }
+        if solutions.len() < limit {
+            self.record(Step::Backtrack { cell: idx });
+        }
+        self.digits[idx] = 0;
+    }
+}
+
+#[must_use]
+pub fn find_solutions_with_peers(
+    givens: &[u8],
+    size: Size,
+    peers: Vec<Vec<usize>>,
+    limit: usize,
+) -> Vec<Vec<u8>> {
+    let mut solver = Solver {
+        size,
+        digits: givens.to_vec(),
+        peers,
+        constraints: Vec::new(),
+        budget: usize::MAX,
+        steps: None,
+        max_steps: usize::MAX,
+    };
+    let mut solutions = Vec::new();
+    if solver.is_consistent() {
+        solver.search(limit, &mut solutions);
+    }
+    solutions
+}
+
+#[must_use]
+pub fn find_solutions(givens: &[u8], variant: &Variant, limit: usize) -> Vec<Vec<u8>> {
+    let mut solver = Solver::new(givens, variant, usize::MAX);
+    let mut solutions = Vec::new();
+    if variant.conflicts(givens).is_empty() {
+        solver.search(limit, &mut solutions);
+    }
+    solutions
+}
+
+#[must_use]
+pub fn has_unique_solution(givens: &[u8], variant: &Variant, budget: usize) -> bool {
+    let mut solver = Solver::new(givens, variant, budget);
+    let mut solutions = Vec::new();
+    if variant.conflicts(givens).is_empty() {
+        solver.search(2, &mut solutions);
+    }
+    solver.budget > 0 && solutions.len() == 1
+}
+
+#[must_use]
+pub fn trace(givens: &[u8], variant: &Variant, max_steps: usize) -> Trace {
+    let mut solver = Solver::new(givens, variant, usize::MAX);
+    solver.steps = Some(Vec::new());
+    solver.max_steps = max_steps;
+    let mut solutions = Vec::new();
+    if variant.conflicts(givens).is_empty() {
+        solver.search(1, &mut solutions);
+    }
+    let truncated = solver.budget == 0;
+    Trace {
+        givens: givens.to_vec(),
+        variant: variant.clone(),
+        steps: solver.steps.unwrap_or_default(),
+        truncated,
+        solution: if truncated {
+            solve(givens, variant).ok()
+        } else {
+            solutions.pop()
+        },
+    }
+}
+
+/// # Errors
+///
+/// Fails with [`Error::NoSolution`] if there is none.
+pub fn solve(givens: &[u8], variant: &Variant) -> Result<Vec<u8>> {
+    find_solutions(givens, variant, 1)
+        .into_iter()
+        .next()
+        .ok_or(Error::NoSolution)
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::size::Size;
+
+    use super::*;
+
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    fn parse(s: &str) -> Result<Vec<u8>> {
+        Size::Nine.parse(s).ok_or(Error::NoSolution)
+    }
+
+    #[test]
+    fn test_solve_classic() -> Result<()> {
+        let givens = parse(&SOLUTION.replacen('5', ".", 4))?;
+        let solution = solve(&givens, &Variant::default())?;
+        assert_eq!(solution, parse(SOLUTION)?);
+        assert_eq!(find_solutions(&givens, &Variant::default(), 2).len(), 1);
+        assert!(has_unique_solution(&givens, &Variant::default(), 100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_killer() -> Result<()> {
+        // every row split into cages of three cells, with a few givens
+        let digits = parse(SOLUTION)?;
+        let mut cages = Vec::new();
+        for row in 0..9 {
+            for third in 0..3 {
+                let cells: Vec<_> = (0..3).map(|j| (row, third * 3 + j)).collect();
+                let sum: u32 = cells
+                    .iter()
+                    .map(|(r, c)| u32::from(digits[r * 9 + c]))
+                    .sum();
+                let cells: String = cells
+                    .iter()
+                    .map(|(r, c)| format!("{}{}", r + 1, c + 1))
+                    .collect();
+                cages.push(format!("k{sum}-{cells}"));
+            }
+        }
+        let variant = Variant::from_str(&cages.join("_"))?;
+        let givens: Vec<u8> = digits
+            .iter()
+            .enumerate()
+            .map(|(i, &digit)| if i % 4 == 0 { digit } else { 0 })
+            .collect();
+        let solution = solve(&givens, &variant)?;
+        for (cells, sum) in variant.cages.iter().map(|cage| (&cage.cells, cage.sum)) {
+            let total: u32 = cells
+                .iter()
+                .map(|(r, c)| u32::from(solution[r * 9 + c]))
+                .sum();
+            assert_eq!(total, u32::from(sum));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_chess() -> Result<()> {
+        let variant = Variant::from_str("knight_king")?;
+        let solution = solve(&[0; 81], &variant)?;
+        for idx in 0..81 {
+            let (row, col) = (idx / 9, idx % 9);
+            for (r, c) in [(1, 2), (2, 1), (1, 1)] {
+                let other = (row + r, col + c);
+                if other.0 < 9 && other.1 < 9 {
+                    assert_ne!(solution[idx], solution[other.0 * 9 + other.1]);
+                }
+                let other = (row + r, col.wrapping_sub(c));
+                if other.0 < 9 && other.1 < 9 {
+                    assert_ne!(solution[idx], solution[other.0 * 9 + other.1]);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_sizes() -> Result<()> {
+        for size in Size::ALL {
+            let variant = Variant::from_str(&format!("s{}", size.digits()))?;
+            let solution = solve(&vec![0; size.cells()], &variant)?;
+            assert!(solution.iter().all(|&digit| digit != 0));
+            for (row, col) in (0..size.digits()).map(|i| (i, i)) {
+                let digit = solution[row * size.digits() + col];
+                assert!(variant
+                    .peers(row, col)
+                    .iter()
+                    .all(|(r, c)| solution[r * size.digits() + c] != digit));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_with_peers() {
+        // four cells that all see each other, like a single house of 4×4
+        let peers: Vec<Vec<usize>> = (0..4)
+            .map(|i| (0..4).filter(|&j| j != i).collect())
+            .collect();
+        let solutions = find_solutions_with_peers(&[0, 2, 0, 0], Size::Four, peers.clone(), 10);
+        assert_eq!(solutions.len(), 6);
+        assert!(solutions.iter().all(|solution| solution[1] == 2));
+        assert!(find_solutions_with_peers(&[2, 2, 0, 0], Size::Four, peers, 10).is_empty());
+    }
+
+    #[test]
+    fn test_inconsistent_givens() -> Result<()> {
+        let mut givens = vec![0; 81];
+        givens[0] = 1;
+        givens[1] = 1;
+        assert!(solve(&givens, &Variant::default()).is_err());
+
+        let variant = Variant::from_str("k3-1112")?;
+        let mut givens = vec![0; 81];
+        givens[0] = 4;
+        assert!(solve(&givens, &variant).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+use crate::{
+    bindings::{get_solver_hotkeys, Hotkey},
+    generator::{CLUE_17_SUDOKUS, EXTREME_SUDOKUS, HARD_SUDOKUS},
+    size::Size,
+    sudoku_data::SudokuData,
+    techniques::{self, find_solution, update_from_digits},
+    variant::Variant,
+    Error, Result,
+};
+
+pub const PUZZLE_SETS: [(&str, &[&str]); 3] = [
+    ("HARD", HARD_SUDOKUS),
+    ("17 CLUE", CLUE_17_SUDOKUS),
+    ("EXTREME", EXTREME_SUDOKUS),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    #[must_use]
+    pub fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort_unstable();
+        Some(Self {
+            min: *samples.first()?,
+            median: samples[samples.len() / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {}, median {}, max {}",
+            techniques::Duration(self.min),
+            techniques::Duration(self.median),
+            techniques::Duration(self.max)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub name: String,
+    pub stats: Stats,
+}
+
+/// # Errors
+///
+/// Fails if the puzzle is not written as 81 cells.
+pub fn load(givens: &str) -> Result<SudokuData> {
+    let digits = Size::Nine.parse(givens).ok_or(Error::ParsePuzzle)?;
+    let mut sudoku = SudokuData::new(Variant::default());
+    update_from_digits(&mut sudoku, &digits, true);
+    Ok(sudoku)
+}
+
+/// # Errors
+///
+/// Fails if a puzzle cannot be read or has no solution.
+pub fn solve_times(puzzles: &[&str]) -> Result<Vec<Duration>> {
+    puzzles
+        .iter()
+        .map(|puzzle| {
+            let givens = Size::Nine.parse(puzzle).ok_or(Error::ParsePuzzle)?;
+            let now = Instant::now();
+            find_solution(&givens, &Variant::default())?;
+            Ok(now.elapsed())
+        })
+        .collect()
+}
+
+/// # Errors
+///
+/// Fails if a puzzle cannot be read, or the technique fails on it.
+pub fn technique_times(hotkey: &Hotkey, puzzles: &[&str]) -> Result<Vec<Duration>> {
+    puzzles
+        .iter()
+        .map(|puzzle| {
+            let mut sudoku = load(puzzle)?;
+            let now = Instant::now();
+            (hotkey.on_click)(&mut sudoku)?;
+            Ok(now.elapsed())
+        })
+        .collect()
+}
+
+/// # Errors
+///
+/// Fails like [`solve_times`] and [`technique_times`].
+pub fn run_suite() -> Result<Vec<Timing>> {
+    let solves = PUZZLE_SETS
+        .iter()
+        .map(|&(name, puzzles)| Ok((format!("SOLVE {name}"), solve_times(puzzles)?)));
+    let techniques = get_solver_hotkeys().into_iter().map(|hotkey| {
+        Ok((
+            hotkey.action.to_string(),
+            technique_times(&hotkey, HARD_SUDOKUS)?,
+        ))
+    });
+    solves
+        .chain(techniques)
+        .filter_map(|timing: Result<(String, Vec<Duration>)>| {
+            timing
+                .map(|(name, samples)| Stats::new(samples).map(|stats| Timing { name, stats }))
+                .transpose()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [3, 1, 4, 1, 5].map(Duration::from_millis).to_vec();
+        let stats = Stats::new(samples);
+        assert_eq!(
+            stats,
+            Some(Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            })
+        );
+        assert_eq!(Stats::new(Vec::new()), None);
+    }
+
+    #[test]
+    fn test_times_every_puzzle() -> Result<()> {
+        let puzzles = &HARD_SUDOKUS[..3];
+        assert_eq!(solve_times(puzzles)?.len(), 3);
+        for hotkey in get_solver_hotkeys() {
+            assert_eq!(technique_times(&hotkey, puzzles)?.len(), 3);
+        }
+        Ok(())
+    }
+}
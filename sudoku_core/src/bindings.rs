@@ -0,0 +1,189 @@
+use crate::{
+    auto::apply_until_stuck,
+    generator::Difficulty,
+    protocol::Operation,
+    size::Size,
+    sudoku_data::{Color, SudokuData},
+    techniques::{
+        check_all_visible_doubles, check_constraints, check_triples, check_x_wings,
+        place_all_hidden_singles, place_all_visible_singles, solve_sudoku, verify_sudoku,
+    },
+    Result,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Hotkey {
+    pub key: &'static str,
+    pub action: &'static str,
+    pub on_click: fn(&mut SudokuData) -> Result<String>,
+    pub operation: Option<fn(&SudokuData) -> Operation>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeneratorHotkey {
+    pub key: &'static str,
+    pub action: &'static str,
+    pub difficulty: Difficulty,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ColorHotkey {
+    pub key: &'static str,
+    pub color: Color,
+}
+
+pub const CLEAR_COLORS_KEY: &str = "U";
+
+pub const REVEAL_CELL_KEY: &str = "O";
+
+pub const CHECK_CELLS_KEY: &str = "P";
+
+#[must_use]
+pub fn get_technique_hotkeys() -> Vec<Hotkey> {
+    vec![
+        Hotkey {
+            key: "A",
+            action: "SINGLES",
+            on_click: place_all_visible_singles,
+            operation: None,
+        },
+        Hotkey {
+            key: "S",
+            action: "HIDDEN",
+            on_click: place_all_hidden_singles,
+            operation: None,
+        },
+        Hotkey {
+            key: "D",
+            action: "DOUBLES",
+            on_click: check_all_visible_doubles,
+            operation: None,
+        },
+        Hotkey {
+            key: "F",
+            action: "TRIPLES",
+            on_click: check_triples,
+            operation: None,
+        },
+        // every letter is taken
+        Hotkey {
+            key: ";",
+            action: "X-WING",
+            on_click: check_x_wings,
+            operation: None,
+        },
+        Hotkey {
+            key: "G",
+            action: "CONSTRAINTS",
+            on_click: check_constraints,
+            operation: None,
+        },
+    ]
+}
+
+#[must_use]
+pub fn get_solver_hotkeys() -> Vec<Hotkey> {
+    let mut hotkeys = get_technique_hotkeys();
+    hotkeys.extend([
+        Hotkey {
+            key: "I",
+            action: "AUTO",
+            on_click: apply_until_stuck,
+            operation: None,
+        },
+        Hotkey {
+            key: "H",
+            action: "SOLVE",
+            on_click: solve_sudoku,
+            operation: Some(Operation::solve),
+        },
+        Hotkey {
+            key: "J",
+            action: "VERIFY",
+            on_click: verify_sudoku,
+            operation: Some(Operation::verify),
+        },
+    ]);
+    hotkeys
+}
+
+#[must_use]
+pub fn get_generator_hotkeys() -> Vec<GeneratorHotkey> {
+    vec![
+        GeneratorHotkey {
+            key: "B",
+            action: "HARD",
+            difficulty: Difficulty::Hard,
+        },
+        GeneratorHotkey {
+            key: "N",
+            action: "17 CLUE",
+            difficulty: Difficulty::Clue17,
+        },
+        GeneratorHotkey {
+            key: "M",
+            action: "EXTREME",
+            difficulty: Difficulty::Extreme,
+        },
+        GeneratorHotkey {
+            key: "X",
+            action: "X-SUDOKU",
+            difficulty: Difficulty::Diagonal,
+        },
+        GeneratorHotkey {
+            key: "Z",
+            action: "4×4",
+            difficulty: Difficulty::Sized(Size::Four),
+        },
+        GeneratorHotkey {
+            key: "C",
+            action: "6×6",
+            difficulty: Difficulty::Sized(Size::Six),
+        },
+        GeneratorHotkey {
+            key: "V",
+            action: "16×16",
+            difficulty: Difficulty::Sized(Size::Sixteen),
+        },
+        GeneratorHotkey {
+            key: "K",
+            action: "JIGSAW",
+            difficulty: Difficulty::Jigsaw,
+        },
+        GeneratorHotkey {
+            key: "L",
+            action: "SAMURAI",
+            difficulty: Difficulty::Samurai,
+        },
+    ]
+}
+
+#[must_use]
+pub fn get_color_hotkeys() -> Vec<ColorHotkey> {
+    vec![
+        ColorHotkey {
+            key: "Q",
+            color: Color::Red,
+        },
+        ColorHotkey {
+            key: "W",
+            color: Color::Orange,
+        },
+        ColorHotkey {
+            key: "E",
+            color: Color::Yellow,
+        },
+        ColorHotkey {
+            key: "R",
+            color: Color::Green,
+        },
+        ColorHotkey {
+            key: "T",
+            color: Color::Blue,
+        },
+        ColorHotkey {
+            key: "Y",
+            color: Color::Purple,
+        },
+    ]
+}
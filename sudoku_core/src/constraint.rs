@@ -0,0 +1,116 @@
+use std::fmt::Debug;
+
+use crate::size::Size;
+
+pub type Pos = (usize, usize);
+
+/// Boards are handed over in row-major order with 0 for empty cells, and
+/// candidates as masks where bit `d` stands for digit `d`.
+pub trait Constraint: Debug {
+    fn houses(&self, _size: Size) -> Vec<Vec<Pos>> {
+        Vec::new()
+    }
+
+    fn separates(&self, _a: Pos, _b: Pos) -> bool {
+        false
+    }
+
+    fn prune(&self, _size: Size, _digits: &[u8], _candidates: &mut [u32]) {}
+
+    fn violations(&self, _size: Size, _digits: &[u8]) -> Vec<Pos> {
+        Vec::new()
+    }
+
+    fn links(&self, _a: Pos, _b: Pos) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rows;
+
+impl Constraint for Rows {
+    fn houses(&self, size: Size) -> Vec<Vec<Pos>> {
+        let n = size.digits();
+        (0..n)
+            .map(|row| (0..n).map(|col| (row, col)).collect())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Columns;
+
+impl Constraint for Columns {
+    fn houses(&self, size: Size) -> Vec<Vec<Pos>> {
+        let n = size.digits();
+        (0..n)
+            .map(|col| (0..n).map(|row| (row, col)).collect())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Regions(pub Vec<Vec<Pos>>);
+
+impl Constraint for Regions {
+    fn houses(&self, _size: Size) -> Vec<Vec<Pos>> {
+        self.0.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Diagonals;
+
+impl Constraint for Diagonals {
+    fn houses(&self, size: Size) -> Vec<Vec<Pos>> {
+        let n = size.digits();
+        vec![
+            (0..n).map(|i| (i, i)).collect(),
+            (0..n).map(|i| (i, n - 1 - i)).collect(),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AntiKnight;
+
+impl Constraint for AntiKnight {
+    fn separates(&self, (row1, col1): Pos, (row2, col2): Pos) -> bool {
+        matches!((row1.abs_diff(row2), col1.abs_diff(col2)), (1, 2) | (2, 1))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AntiKing;
+
+impl Constraint for AntiKing {
+    fn separates(&self, (row1, col1): Pos, (row2, col2): Pos) -> bool {
+        matches!(
+            (row1.abs_diff(row2), col1.abs_diff(col2)),
+            (0, 1) | (1, 0 | 1)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_houses() {
+        let size = Size::Six;
+        assert_eq!(Rows.houses(size)[2][5], (2, 5));
+        assert_eq!(Columns.houses(size)[2][5], (5, 2));
+        assert_eq!(Diagonals.houses(size)[1][0], (0, 5));
+    }
+
+    #[test]
+    fn test_chess_moves() {
+        assert!(AntiKnight.separates((4, 4), (2, 5)));
+        assert!(!AntiKnight.separates((4, 4), (3, 3)));
+        assert!(AntiKing.separates((4, 4), (3, 3)));
+        assert!(!AntiKing.separates((4, 4), (4, 4)));
+        assert!(!AntiKing.separates((4, 4), (2, 4)));
+    }
+}
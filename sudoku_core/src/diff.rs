@@ -0,0 +1,84 @@
+use std::fmt::Display;
+
+use crate::{
+    constraint::Pos,
+    sudoku_data::{Cell, SudokuData},
+};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Changes {
+    pub placed: Vec<(Pos, u8)>,
+    pub eliminated: Vec<(Pos, u8)>,
+}
+
+impl Changes {
+    #[must_use]
+    pub fn between(before: &SudokuData, after: &SudokuData) -> Self {
+        let n = after.size().digits();
+        let mut changes = Self::default();
+        for pos in (0..n).flat_map(|row| (0..n).map(move |col| (row, col))) {
+            let Cell::Empty { choices: old } = before.get(pos.0, pos.1) else {
+                continue;
+            };
+            match after.get(pos.0, pos.1) {
+                Cell::Empty { choices } => changes.eliminated.extend(
+                    (0..n)
+                        .filter(|&i| old[i] && !choices[i])
+                        .map(|i| (pos, i as u8 + 1)),
+                ),
+                cell => changes
+                    .placed
+                    .extend(cell.value().map(|value| (pos, value))),
+            }
+        }
+        changes
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.placed.is_empty() && self.eliminated.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_placed(&self, pos: Pos) -> bool {
+        self.placed.iter().any(|&(cell, _)| cell == pos)
+    }
+
+    #[must_use]
+    pub fn is_eliminated(&self, pos: Pos, digit: u8) -> bool {
+        self.eliminated.contains(&(pos, digit))
+    }
+}
+
+impl Display for Changes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} placed, {} eliminated",
+            self.placed.len(),
+            self.eliminated.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_between() {
+        let before = SudokuData::default();
+        let mut after = before.clone();
+        after.set(0, 0, 5, false);
+        after.set_choice(8, 8, 1, false);
+        let changes = Changes::between(&before, &after);
+        assert_eq!(changes.placed, [((0, 0), 5)]);
+        assert!(changes.is_placed((0, 0)));
+        // the 5 is gone from the 20 peers of the first cell
+        assert_eq!(changes.eliminated.len(), 21);
+        assert!(changes.is_eliminated((0, 8), 5));
+        assert!(changes.is_eliminated((8, 8), 1));
+        assert_eq!(changes.to_string(), "1 placed, 21 eliminated");
+        assert!(Changes::between(&after, &after).is_empty());
+    }
+}
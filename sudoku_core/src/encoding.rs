@@ -0,0 +1,150 @@
+use rust_sudoku_solver::Sudoku;
+
+#[must_use]
+pub fn compress_string(s: &str) -> Option<String> {
+    let mut compressed = String::new();
+    let mut count = 0;
+    let Some(mut last_char) = s.chars().next() else {
+        return Some(compressed);
+    };
+
+    for c in s.chars() {
+        if c == last_char {
+            count = push_if_full(&mut compressed, c, count)?;
+        } else {
+            push_repeated(&mut compressed, last_char, count)?;
+            last_char = c;
+            count = 1;
+        }
+    }
+    push_repeated(&mut compressed, last_char, count)?;
+    Some(compressed)
+}
+
+/// Letters stand for repeat counts, so the hex digits of 16×16 boards are
+/// written with this character in front of them.
+const ESCAPE: char = '!';
+
+fn push_if_full(compressed: &mut String, c: char, count: usize) -> Option<usize> {
+    if count == 52 {
+        push_char(compressed, c);
+        compressed.push(get_letter(count - 1)?);
+        Some(1)
+    } else {
+        Some(count + 1)
+    }
+}
+
+fn push_repeated(compressed: &mut String, c: char, count: usize) -> Option<()> {
+    push_char(compressed, c);
+    if count > 1 {
+        compressed.push(get_letter(count - 1)?);
+    }
+    Some(())
+}
+
+fn push_char(compressed: &mut String, c: char) {
+    if get_count(c).is_some() {
+        compressed.push(ESCAPE);
+    }
+    compressed.push(c);
+}
+
+#[must_use]
+pub fn decompress_string(s: &str) -> Option<String> {
+    let mut decompressed = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == ESCAPE {
+            decompressed.push(chars.next()?);
+        } else if let Some(idx) = get_count(c) {
+            let letter = decompressed.pop()?;
+            decompressed.push_str(&letter.to_string().repeat(idx + 1));
+        } else {
+            decompressed.push(c);
+        }
+    }
+    Some(decompressed)
+}
+
+const fn get_count(c: char) -> Option<usize> {
+    match c {
+        'a'..='z' => Some(c as usize - 'a' as usize),
+        'A'..='Z' => Some(c as usize - 'A' as usize + 26),
+        _ => None,
+    }
+}
+
+const fn get_letter(idx: usize) -> Option<char> {
+    match idx {
+        // start with lowercase, then uppercase
+        0..=25 => Some(('a' as usize + idx) as u8 as char),
+        26..=52 => Some(('A' as usize + idx - 26) as u8 as char),
+        _ => None,
+    }
+}
+
+#[must_use]
+pub fn to_sudoku(digits: &[u8]) -> Sudoku {
+    let mut sudoku = Sudoku::default();
+    for (idx, &digit) in digits.iter().enumerate() {
+        if digit != 0 {
+            sudoku.place(idx, usize::from(digit));
+        }
+    }
+    sudoku
+}
+
+#[must_use]
+pub fn sudoku_digits(sudoku: &Sudoku) -> Vec<u8> {
+    sudoku.digits.iter().map(|&digit| digit as u8).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", Some(""))]
+    #[case("1", Some("1"))]
+    #[case("11", Some("1b"))]
+    #[case("111111111", Some("1i"))]
+    #[case("111111112", Some("1h2"))]
+    #[case("111111122", Some("1g2b"))]
+    #[case("111111222", Some("1f2c"))]
+    #[case("111112222", Some("1e2d"))]
+    #[case("111122222", Some("1d2e"))]
+    #[case("111222222", Some("1c2f"))]
+    #[case("112222222", Some("1b2g"))]
+    #[case("122222222", Some("12h"))]
+    #[case("222222222", Some("2i"))]
+    #[case("1....2", Some("1.d2"))]
+    #[case("AA.F", Some("!Ab.!F"))]
+    fn test_compress_string(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(compress_string(input), expected.map(ToString::to_string));
+    }
+
+    #[rstest]
+    #[case("", Some(""))]
+    #[case("1", Some("1"))]
+    #[case("1b", Some("11"))]
+    #[case("1i", Some("111111111"))]
+    #[case("1h2", Some("111111112"))]
+    #[case("1g2b", Some("111111122"))]
+    #[case("1f2c", Some("111111222"))]
+    #[case("1e2d", Some("111112222"))]
+    #[case("1d2e", Some("111122222"))]
+    #[case("1c2f", Some("111222222"))]
+    #[case("1b2g", Some("112222222"))]
+    #[case("12h", Some("122222222"))]
+    #[case("2i", Some("222222222"))]
+    #[case("1.d2", Some("1....2"))]
+    #[case("d", None)]
+    #[case("!Ab.!F", Some("AA.F"))]
+    #[case("!", None)]
+    fn test_decompress_string(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(decompress_string(input), expected.map(ToString::to_string));
+    }
+}
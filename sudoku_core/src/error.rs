@@ -0,0 +1,43 @@
+use std::fmt::{Display, Formatter};
+
+use derive_more::From;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, From)]
+pub enum Error {
+    #[from]
+    Sudoku(rust_sudoku_solver::Error),
+    #[from]
+    Utf8(std::string::FromUtf8Error),
+    #[from]
+    ParseInt(std::num::ParseIntError),
+    GenerateSudoku,
+    ParseVariant,
+    ParsePuzzle,
+    NoSolution,
+    UnsupportedSize,
+    UnsupportedRegions,
+    UnsupportedVariant,
+    Worker(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Self::Sudoku(e) => write!(f, "{e}"),
+            Self::Utf8(e) => write!(f, "Invalid UTF-8: {e}"),
+            Self::ParseInt(e) => write!(f, "Invalid number: {e}"),
+            Self::GenerateSudoku => write!(f, "Failed to generate sudoku"),
+            Self::ParseVariant => write!(f, "Invalid variant definition"),
+            Self::ParsePuzzle => write!(f, "Invalid puzzle definition"),
+            Self::NoSolution => write!(f, "No solution found"),
+            Self::UnsupportedSize => write!(f, "Not available for this board size"),
+            Self::UnsupportedRegions => write!(f, "Not available for jigsaw boards"),
+            Self::UnsupportedVariant => write!(f, "Only available for classic rules"),
+            Self::Worker(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
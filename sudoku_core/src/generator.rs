@@ -1,9 +1,12 @@
 use rand::seq::SliceRandom;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-use crate::{Error, Result};
+use std::str::FromStr;
 
-const HARD_SUDOKUS: &[&str] = &[
+use crate::{backtrack, size::Size, variant::Variant, Error, Result};
+
+pub const HARD_SUDOKUS: &[&str] = &[
     "....754..........8.8.19....3....1.6........34....6817.2.4...6.39......2.53.2.....",
     "3.........5.7.3..8....28.7.7......43...........39.41.54..3..8..1...4....968...2..",
     "3.26.9..55..73..........9.....94..........1.9....57.6...85....6........3.19.82.4.",
@@ -56,7 +59,7 @@ const HARD_SUDOKUS: &[&str] = &[
     ".1....4...72....934.9...76...62.89..8....657......9....5......1..4.3......3..5...",
 ];
 
-const CLUE_17_SUDOKUS: &[&str] = &[
+pub const CLUE_17_SUDOKUS: &[&str] = &[
     ".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...",
     ".......1.4.........2...........5.6.4..8...3....1.9....3..4..2...5.1........8.7...",
     ".......12....35......6...7.7.....3.....4..8..1...........12.....8.....4..5....6..",
@@ -108,7 +111,7 @@ const CLUE_17_SUDOKUS: &[&str] = &[
     ".......153..6............8.6...5.2.......1..........4..1.2..7.....76.3....8......",
 ];
 
-const EXTREME_SUDOKUS: &[&str] = &[
+pub const EXTREME_SUDOKUS: &[&str] = &[
     "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3",
     "........2..8.1.9..5....3.4....1.93...6..3..8...37......4......53.1.7.8..2........",
     "..2...7...1.....6.5......18....37.......49.....41.23....3.2.9...8.....5.6.......2",
@@ -161,29 +164,137 @@ const EXTREME_SUDOKUS: &[&str] = &[
     "..9...4...7.3...2.8...6...71..8....6....1..7.....56...3....5..1.4.....9...2...7..",
 ];
 
-#[derive(Debug, Clone, Copy)]
+const JIGSAW_SUDOKUS: &[(&str, &str)] = &[
+    (
+        "111222233412223233411253333411155556447555666447566666447888888777898989777999999",
+        ".3..6.1...1.....6...5....374..213.5.5.7.9...1......2....36.2.......8.91..........",
+    ),
+    (
+        "111122233412222333411252363411555363444456666747555966747588996777788999788888999",
+        ".7.82..912..9.....6......72...394.....3.8..2..48.....9..5.7.........5....8.....4.",
+    ),
+    (
+        "111111633122222663112255633444225633477555633477585669447885699477788999478888999",
+        "87...1...2.69......9...........79.68.........4..1...5...1.6.24....8..5.7...715...",
+    ),
+    (
+        "111223333141522363141522263144526663144525663447555566477788888777889999778899999",
+        "...86.94...49...51...21.73....4..2...97..8...........82....3.....8.27...4........",
+    ),
+    (
+        "122222233111123333411122633445155636475555666477755686447888886447898999777899999",
+        ".........7..4..6...3.1..2.8.....579.....7..1..6.....34....21.83......1..1.2...3.5",
+    ),
+    (
+        "111122333111222333142252633144452663447555666477555699477588669478888899777889999",
+        ".8..29..1...3.78..3.......4.......124.....3......8.9...4....7...17.3.68.8..2.....",
+    ),
+];
+
+pub const SAMURAI_SUDOKUS: &[&str] = &[
+    "...8......4..7..8...7..6....1..3.6..2...7.18.59..213...2.53.6.86.37...9...6...45.....4.........4..9.5....................8.1.......94......35......8..8.3.9....2....9..84......285.77.......8..9.4....1....326..5.9....2..7..................9..68.......3.........21..5.76.23....3...8.7.........9.7.......5.....5..9.......87.5...7...6.5....4.8.9.6.4..5.1......6...7.....8..6",
+    ".1.3...6...34.72....9...4.3.2..3.6...5....7...5.....8..4........81.4.....6.8.4.312.....39..7...192...97..........8....42.....3..........5.......12...87...65...5..............71...9..........2..6.8....2........5..6...1...................7.3..912...........6..................5.8...84..7.2.....84..12.79...6.74...1.....98.347...4.....3.1..7....2...9.76.....5.8...1......2",
+    ".5.7.....2...41....9.14.3..49......37....9......3.......4.....2.....43.......31.......8......48.65...563..4.67..................8.2.......2.....56...793.........86...2.1.......1.....5..7....4..9...5........2.......5.......5.........8..9...687.9..6...1......6.134....817..1.54..9.....4..958.......4......4.........7.51...3..........4...9..2.1.7.........4...5..3.437..2..",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Difficulty {
     Hard,
     Clue17,
     Extreme,
+    Diagonal,
+    Sized(Size),
+    Jigsaw,
+    Samurai,
 }
 
-#[allow(unused)]
-pub fn get_random_sudoku(difficulty: Difficulty) -> Result<String> {
+/// # Errors
+///
+/// Fails if no puzzle of that kind could be made.
+pub fn get_random_sudoku(difficulty: Difficulty) -> Result<(String, Variant)> {
     let sudokus = match difficulty {
         Difficulty::Hard => HARD_SUDOKUS,
         Difficulty::Clue17 => CLUE_17_SUDOKUS,
         Difficulty::Extreme => EXTREME_SUDOKUS,
+        // shuffling rows and columns would break the diagonals, and there are
+        // no bundled puzzles of other sizes
+        Difficulty::Diagonal => {
+            return generated(Variant {
+                diagonal: true,
+                ..Variant::default()
+            });
+        }
+        Difficulty::Sized(size) => {
+            return generated(Variant {
+                size,
+                ..Variant::default()
+            });
+        }
+        Difficulty::Jigsaw => return get_random_jigsaw(),
+        Difficulty::Samurai => return get_random_samurai(),
     };
     let sudoku = sudokus
         .choose(&mut rand::thread_rng())
         .ok_or(Error::GenerateSudoku)?;
     let arr = &mut sudoku.as_bytes().to_vec();
     transmute(arr);
-    Ok(String::from_utf8(arr.clone())?)
+    Ok((String::from_utf8(arr.clone())?, Variant::default()))
+}
+
+fn generated(variant: Variant) -> Result<(String, Variant)> {
+    Ok((generate_sudoku(&variant)?, variant))
+}
+
+fn get_random_jigsaw() -> Result<(String, Variant)> {
+    let (regions, sudoku) = JIGSAW_SUDOKUS
+        .choose(&mut rand::thread_rng())
+        .ok_or(Error::GenerateSudoku)?;
+    let variant = Variant::from_str(&format!("j{regions}"))?;
+    let arr = &mut sudoku.as_bytes().to_vec();
+    swap_random_digits(arr);
+    Ok((String::from_utf8(arr.clone())?, variant))
 }
 
-fn transmute(sudoku: &mut [u8]) {
+fn get_random_samurai() -> Result<(String, Variant)> {
+    let sudoku = SAMURAI_SUDOKUS
+        .choose(&mut rand::thread_rng())
+        .ok_or(Error::GenerateSudoku)?;
+    let arr = &mut sudoku.as_bytes().to_vec();
+    swap_random_digits(arr);
+    Ok((String::from_utf8(arr.clone())?, Variant::default()))
+}
+
+/// A given whose removal needs more guesses than this to check is kept, which
+/// keeps generating large boards fast at the cost of a few extra givens.
+const UNIQUENESS_BUDGET: usize = 1_000;
+
+/// # Errors
+///
+/// Fails if the seeded grid can not be solved under the rules of `variant`.
+pub fn generate_sudoku(variant: &Variant) -> Result<String> {
+    let size = variant.size;
+    let n = size.digits();
+    let mut digits: Vec<u8> = (1..=n as u8).collect();
+    digits.shuffle(&mut rand::thread_rng());
+    let mut seed = vec![0; size.cells()];
+    for (i, &digit) in digits.iter().enumerate() {
+        seed[i * (n + 1)] = digit;
+    }
+    let mut puzzle = backtrack::solve(&seed, variant)?;
+
+    let mut order: Vec<usize> = (0..size.cells()).collect();
+    order.shuffle(&mut rand::thread_rng());
+    for idx in order {
+        let digit = puzzle[idx];
+        puzzle[idx] = 0;
+        if !backtrack::has_unique_solution(&puzzle, variant, UNIQUENESS_BUDGET) {
+            puzzle[idx] = digit;
+        }
+    }
+    Ok(size.format(&puzzle))
+}
+
+pub fn transmute(sudoku: &mut [u8]) {
     let actions = [
         swap_random_block_rows,
         swap_random_block_cols,
@@ -272,9 +383,10 @@ fn swap(sudoku: &mut [u8], (row1, col1): (usize, usize), (row2, col2): (usize, u
 #[cfg(test)]
 #[allow(clippy::panic_in_result_fn)]
 mod tests {
-    use crate::Result;
+    use crate::{samurai::Samurai, Result};
+    use rstest::rstest;
     use rust_sudoku_solver::{solver, Sudoku};
-    use std::{collections::HashSet, str::FromStr};
+    use std::collections::HashSet;
 
     use super::*;
 
@@ -298,7 +410,7 @@ mod tests {
     #[test]
     fn test_solve_random_sudokus() -> Result<()> {
         for _ in 0..1_000 {
-            let sudoku = get_random_sudoku(Difficulty::Hard)?;
+            let (sudoku, _) = get_random_sudoku(Difficulty::Hard)?;
             let s = Sudoku::from_str(&sudoku)?;
             let solution = solver::solve(s)?;
             assert!(solution.is_solved());
@@ -306,11 +418,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_generate_diagonal_sudoku() -> Result<()> {
+        let (sudoku, variant) = get_random_sudoku(Difficulty::Diagonal)?;
+        assert!(variant.diagonal);
+        let givens = Size::Nine.parse(&sudoku).ok_or(Error::GenerateSudoku)?;
+        let solutions = backtrack::find_solutions(&givens, &variant, 2);
+        assert_eq!(solutions.len(), 1);
+        let main: HashSet<_> = (0..9).map(|i| solutions[0][i * 10]).collect();
+        let anti: HashSet<_> = (0..9).map(|i| solutions[0][i * 8 + 8]).collect();
+        assert_eq!(main.len(), 9);
+        assert_eq!(anti.len(), 9);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Size::Four)]
+    #[case(Size::Six)]
+    fn test_generate_sized_sudoku(#[case] size: Size) -> Result<()> {
+        let (sudoku, variant) = get_random_sudoku(Difficulty::Sized(size))?;
+        assert_eq!(variant.size, size);
+        let givens = size.parse(&sudoku).ok_or(Error::GenerateSudoku)?;
+        assert!(backtrack::has_unique_solution(
+            &givens,
+            &variant,
+            usize::MAX
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_jigsaw_sudokus() -> Result<()> {
+        for (regions, sudoku) in JIGSAW_SUDOKUS {
+            let variant = Variant::from_str(&format!("j{regions}"))?;
+            let givens = Size::Nine.parse(sudoku).ok_or(Error::GenerateSudoku)?;
+            assert_eq!(backtrack::find_solutions(&givens, &variant, 2).len(), 1);
+        }
+        let (sudoku, variant) = get_random_sudoku(Difficulty::Jigsaw)?;
+        let givens = Size::Nine.parse(&sudoku).ok_or(Error::GenerateSudoku)?;
+        assert!(backtrack::solve(&givens, &variant).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_samurai_sudokus() -> Result<()> {
+        let (sudoku, _) = get_random_sudoku(Difficulty::Samurai)?;
+        assert!(Samurai::from_str(&sudoku)?.solution().is_ok());
+        Ok(())
+    }
+
     #[test]
     fn test_number_of_sudokus() -> Result<()> {
         let mut unique = HashSet::new();
         for _ in 0..1_000 {
-            let s = get_random_sudoku(Difficulty::Clue17)?;
+            let (s, _) = get_random_sudoku(Difficulty::Clue17)?;
             unique.insert(s);
         }
         assert_eq!(unique.len(), 1_000);
@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use serde_compact::compact;
+use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc, str::FromStr};
+
+use crate::{
+    constraint::{Constraint, Pos},
+    size::Size,
+    variant, Error, Result,
+};
+
+#[compact]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cage {
+    pub cells: Vec<(usize, usize)>,
+    pub sum: u8,
+}
+
+impl Cage {
+    #[must_use]
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        self.cells.contains(&(row, col))
+    }
+
+    #[must_use]
+    pub fn anchor(&self) -> Option<(usize, usize)> {
+        self.cells.iter().min().copied()
+    }
+
+    /// Masks have bit `i` for digit `i + 1` here, unlike the candidates of
+    /// [`Constraint::prune`].
+    #[must_use]
+    pub fn possible_digits(&self, placed: u16, available: u16, digits: usize) -> u16 {
+        cached_combinations(u32::from(self.sum), self.cells.len(), digits)
+            .iter()
+            .copied()
+            .filter(|&combination| combination & placed == placed)
+            .map(|combination| combination & !placed)
+            .filter(|&remaining| remaining & !available == 0)
+            .fold(0, |possible, remaining| possible | remaining)
+    }
+}
+
+fn placed_digits(cells: &[Pos], size: Size, digits: &[u8]) -> u16 {
+    cells
+        .iter()
+        .map(|&(row, col)| digits[row * size.digits() + col])
+        .filter(|&digit| digit != 0)
+        .fold(0, |placed, digit| placed | 1 << (digit - 1))
+}
+
+impl Constraint for Cage {
+    fn houses(&self, _size: Size) -> Vec<Vec<Pos>> {
+        vec![self.cells.clone()]
+    }
+
+    fn prune(&self, size: Size, digits: &[u8], candidates: &mut [u32]) {
+        let n = size.digits();
+        let empty: Vec<_> = self
+            .cells
+            .iter()
+            .map(|&(row, col)| row * n + col)
+            .filter(|&idx| digits[idx] == 0)
+            .collect();
+        let available = empty
+            .iter()
+            .fold(0, |available, &idx| available | candidates[idx] >> 1);
+        let placed = placed_digits(&self.cells, size, digits);
+        let possible = u32::from(self.possible_digits(placed, available as u16, n)) << 1;
+        for idx in empty {
+            candidates[idx] &= possible;
+        }
+    }
+
+    fn violations(&self, size: Size, digits: &[u8]) -> Vec<Pos> {
+        let placed = placed_digits(&self.cells, size, digits);
+        let reachable = cached_combinations(u32::from(self.sum), self.cells.len(), size.digits())
+            .iter()
+            .any(|combination| combination & placed == placed);
+        if reachable {
+            return Vec::new();
+        }
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&(row, col)| digits[row * size.digits() + col] != 0)
+            .collect()
+    }
+
+    fn links(&self, a: Pos, b: Pos) -> bool {
+        self.cells.contains(&a) && self.cells.contains(&b)
+    }
+}
+
+#[must_use]
+pub fn combinations(sum: u32, size: usize, digits: usize) -> Vec<u16> {
+    (0..=u16::MAX >> (16 - digits))
+        .filter(|mask| mask.count_ones() as usize == size)
+        .filter(|&mask| digit_sum(mask) == sum)
+        .collect()
+}
+
+thread_local! {
+    static COMBINATIONS: RefCell<HashMap<(u32, usize, usize), Rc<[u16]>>> = RefCell::default();
+}
+
+// pruning runs on every node of the search, too often to go through all
+// masks each time
+fn cached_combinations(sum: u32, size: usize, digits: usize) -> Rc<[u16]> {
+    COMBINATIONS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let combinations = cache
+            .entry((sum, size, digits))
+            .or_insert_with(|| combinations(sum, size, digits).into());
+        Rc::clone(combinations)
+    })
+}
+
+fn digit_sum(mask: u16) -> u32 {
+    (0..16)
+        .filter(|i| mask & (1 << i) != 0)
+        .map(|i| i + 1)
+        .sum()
+}
+
+impl FromStr for Cage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (sum, cells) = s.split_once('-').ok_or(Error::ParseVariant)?;
+        let sum = sum.parse()?;
+        let cells = variant::parse_cells(cells)?;
+        if cells.is_empty() {
+            return Err(Error::ParseVariant);
+        }
+        Ok(Self { cells, sum })
+    }
+}
+
+impl Display for Cage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.sum, variant::format_cells(&self.cells))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(3, 2, &[0b11])]
+    #[case(4, 2, &[0b101])]
+    #[case(17, 2, &[0b1_1000_0000])]
+    #[case(45, 9, &[0b1_1111_1111])]
+    #[case(2, 2, &[])]
+    fn test_combinations(#[case] sum: u32, #[case] size: usize, #[case] expected: &[u16]) {
+        assert_eq!(combinations(sum, size, 9), expected);
+    }
+
+    #[test]
+    fn test_cached_combinations() {
+        let first = cached_combinations(10, 2, 9);
+        assert_eq!(*first, *combinations(10, 2, 9));
+        assert!(Rc::ptr_eq(&first, &cached_combinations(10, 2, 9)));
+        assert!(!Rc::ptr_eq(&first, &cached_combinations(10, 2, 16)));
+    }
+
+    #[test]
+    fn test_possible_digits() -> Result<()> {
+        let cage = Cage::from_str("10-111213")?;
+        // 10 as three digits: 127, 136, 145, 235
+        assert_eq!(cage.possible_digits(0, 0b1_1111_1111, 9), 0b111_1111);
+        // with a 1 placed, the rest is 27, 36 or 45
+        assert_eq!(cage.possible_digits(0b1, 0b1_1111_1110, 9), 0b111_1110);
+        // without a 7 available, 127 is ruled out
+        assert_eq!(cage.possible_digits(0b1, 0b0_1011_1110, 9), 0b011_1100);
+        // 8 as three digits is 125 or 134, but only 134 fits on a 4×4 board
+        let cage = Cage::from_str("8-111213")?;
+        assert_eq!(cage.possible_digits(0, 0b1_1111_1111, 9), 0b1_1111);
+        assert_eq!(cage.possible_digits(0, 0b1111, 4), 0b1101);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cage_constraint() -> Result<()> {
+        let cage = Cage::from_str("4-1112")?;
+        let mut digits = vec![0; 81];
+        let mut candidates = vec![0b11_1111_1110; 81];
+        cage.prune(Size::Nine, &digits, &mut candidates);
+        assert_eq!(candidates[0], 0b1010);
+        assert_eq!(candidates[2], 0b11_1111_1110);
+
+        digits[0] = 2;
+        assert_eq!(cage.violations(Size::Nine, &digits), vec![(0, 0)]);
+        digits[0] = 3;
+        assert!(cage.violations(Size::Nine, &digits).is_empty());
+        digits[1] = 2;
+        assert_eq!(cage.violations(Size::Nine, &digits), vec![(0, 0), (0, 1)]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("10-1112")]
+    #[case("45-111213141516171819")]
+    fn test_cage_roundtrip(#[case] input: &str) -> Result<()> {
+        assert_eq!(Cage::from_str(input)?.to_string(), input);
+        Ok(())
+    }
+}
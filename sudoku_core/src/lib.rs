@@ -0,0 +1,59 @@
+#![warn(
+    // missing_docs,
+    // unreachable_pub,
+    keyword_idents,
+    unexpected_cfgs,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    unused_crate_dependencies,
+    unused_extern_crates,
+    unused_import_braces,
+    future_incompatible,
+    nonstandard_style,
+    bad_style,
+    dead_code,
+    improper_ctypes,
+    non_shorthand_field_patterns,
+    no_mangle_generic_items,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true,
+)]
+
+pub mod actions;
+pub mod auto;
+pub mod backtrack;
+pub mod benchmark;
+pub mod bindings;
+pub mod constraint;
+pub mod diff;
+pub mod encoding;
+pub mod error;
+pub mod generator;
+pub mod killer;
+pub mod lines;
+pub mod protocol;
+pub mod puzzle;
+pub mod rating;
+pub mod samurai;
+pub mod size;
+pub mod solution_path;
+pub mod state;
+pub mod sudoku_data;
+pub mod symmetry;
+pub mod techniques;
+#[cfg(test)]
+mod test_support;
+pub mod trace;
+pub mod variant;
+
+pub use error::Error;
+pub use error::Result;
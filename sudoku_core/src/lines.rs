@@ -0,0 +1,379 @@
+use serde::{Deserialize, Serialize};
+use serde_compact::compact;
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    constraint::{Constraint, Pos},
+    size::Size,
+    variant, Error, Result,
+};
+
+#[compact]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Thermometer {
+    pub cells: Vec<Pos>,
+}
+
+#[compact]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Arrow {
+    pub cells: Vec<Pos>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DotKind {
+    White,
+    Black,
+}
+
+#[compact]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dot {
+    pub kind: DotKind,
+    pub cells: [Pos; 2],
+}
+
+fn bounds(size: Size, digits: &[u8], candidates: &[u32], (row, col): Pos) -> (u32, u32) {
+    let idx = row * size.digits() + col;
+    match digits[idx] {
+        0 if candidates[idx] == 0 => (size.digits() as u32 + 1, 0),
+        0 => (
+            candidates[idx].trailing_zeros(),
+            31 - candidates[idx].leading_zeros(),
+        ),
+        digit => (u32::from(digit), u32::from(digit)),
+    }
+}
+
+fn digit_range(low: u32, high: u32) -> u32 {
+    (low.max(1)..=high.min(31)).fold(0, |mask, digit| mask | 1 << digit)
+}
+
+fn digit_at(size: Size, digits: &[u8], (row, col): Pos) -> u8 {
+    digits[row * size.digits() + col]
+}
+
+fn is_connected(cells: &[Pos]) -> bool {
+    cells.windows(2).all(|pair| {
+        let (dr, dc) = (pair[0].0.abs_diff(pair[1].0), pair[0].1.abs_diff(pair[1].1));
+        dr <= 1 && dc <= 1 && (dr, dc) != (0, 0)
+    })
+}
+
+fn parse_line(s: &str) -> Result<Vec<Pos>> {
+    let cells = variant::parse_cells(s)?;
+    if cells.len() < 2 || !is_connected(&cells) {
+        return Err(Error::ParseVariant);
+    }
+    Ok(cells)
+}
+
+impl Constraint for Thermometer {
+    fn houses(&self, _size: Size) -> Vec<Vec<Pos>> {
+        vec![self.cells.clone()]
+    }
+
+    fn prune(&self, size: Size, digits: &[u8], candidates: &mut [u32]) {
+        let bounds: Vec<_> = self
+            .cells
+            .iter()
+            .map(|&pos| bounds(size, digits, candidates, pos))
+            .collect();
+        let mut lows = Vec::with_capacity(bounds.len());
+        for (i, &(low, _)) in bounds.iter().enumerate() {
+            let previous = if i == 0 { 0 } else { lows[i - 1] };
+            lows.push(low.max(previous + 1));
+        }
+        let mut highs = vec![0; bounds.len()];
+        for (i, &(_, high)) in bounds.iter().enumerate().rev() {
+            let next = highs.get(i + 1).copied().unwrap_or(u32::MAX);
+            highs[i] = high.min(next.saturating_sub(1));
+        }
+        for (i, &(row, col)) in self.cells.iter().enumerate() {
+            let idx = row * size.digits() + col;
+            if digits[idx] == 0 {
+                candidates[idx] &= digit_range(lows[i], highs[i]);
+            }
+        }
+    }
+
+    fn violations(&self, size: Size, digits: &[u8]) -> Vec<Pos> {
+        let n = size.digits();
+        let placed: Vec<_> = self
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| (i, pos, usize::from(digit_at(size, digits, pos))))
+            .filter(|&(_, _, digit)| digit != 0)
+            .collect();
+        let mut violations = Vec::new();
+        for &(i, pos, digit) in &placed {
+            let room = digit > i && n - digit >= self.cells.len() - 1 - i;
+            let ordered = placed
+                .iter()
+                .all(|&(j, _, other)| j <= i || other >= digit + (j - i));
+            let ordered_before = placed
+                .iter()
+                .all(|&(j, _, other)| j >= i || digit >= other + (i - j));
+            if !room || !ordered || !ordered_before {
+                violations.push(pos);
+            }
+        }
+        violations
+    }
+
+    fn links(&self, a: Pos, b: Pos) -> bool {
+        self.cells.contains(&a) && self.cells.contains(&b)
+    }
+}
+
+impl Constraint for Arrow {
+    fn prune(&self, size: Size, digits: &[u8], candidates: &mut [u32]) {
+        let (circle, shaft) = (self.cells[0], &self.cells[1..]);
+        let (circle_low, circle_high) = bounds(size, digits, candidates, circle);
+        let shaft_bounds: Vec<_> = shaft
+            .iter()
+            .map(|&pos| bounds(size, digits, candidates, pos))
+            .collect();
+        let shaft_low: u32 = shaft_bounds.iter().map(|&(low, _)| low).sum();
+        let shaft_high: u32 = shaft_bounds.iter().map(|&(_, high)| high).sum();
+
+        let n = size.digits();
+        let (row, col) = circle;
+        if digits[row * n + col] == 0 {
+            candidates[row * n + col] &= digit_range(shaft_low, shaft_high);
+        }
+        for (&(row, col), &(low, high)) in shaft.iter().zip(&shaft_bounds) {
+            let idx = row * n + col;
+            if digits[idx] == 0 {
+                let others_low = shaft_low.saturating_sub(low);
+                let others_high = shaft_high.saturating_sub(high);
+                candidates[idx] &= digit_range(
+                    circle_low.saturating_sub(others_high),
+                    circle_high.saturating_sub(others_low),
+                );
+            }
+        }
+    }
+
+    fn violations(&self, size: Size, digits: &[u8]) -> Vec<Pos> {
+        let n = size.digits() as u32;
+        let shaft = self.cells[1..]
+            .iter()
+            .map(|&pos| digit_at(size, digits, pos));
+        let empty = shaft.clone().filter(|&digit| digit == 0).count() as u32;
+        let placed: u32 = shaft.map(u32::from).sum();
+        let (low, high) = (placed + empty, placed + empty * n);
+        let fits = match u32::from(digit_at(size, digits, self.cells[0])) {
+            0 => low <= n,
+            circle => (low..=high).contains(&circle),
+        };
+        if fits {
+            return Vec::new();
+        }
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&pos| digit_at(size, digits, pos) != 0)
+            .collect()
+    }
+
+    fn links(&self, a: Pos, b: Pos) -> bool {
+        self.cells.contains(&a) && self.cells.contains(&b)
+    }
+}
+
+impl Dot {
+    /// # Errors
+    ///
+    /// Fails with [`Error::ParseVariant`] unless `s` holds two neighbouring
+    /// cells.
+    pub fn parse(kind: DotKind, s: &str) -> Result<Self> {
+        match variant::parse_cells(s)?[..] {
+            [a, b] if a.0.abs_diff(b.0) + a.1.abs_diff(b.1) == 1 => Ok(Self {
+                kind,
+                cells: [a, b],
+            }),
+            _ => Err(Error::ParseVariant),
+        }
+    }
+
+    const fn allows(&self, a: u32, b: u32) -> bool {
+        match self.kind {
+            DotKind::White => a.abs_diff(b) == 1,
+            DotKind::Black => a == 2 * b || b == 2 * a,
+        }
+    }
+
+    #[must_use]
+    pub fn centre(&self) -> (f64, f64) {
+        let [(row1, col1), (row2, col2)] = self.cells;
+        (
+            (col1 + col2 + 1) as f64 / 2.0,
+            (row1 + row2 + 1) as f64 / 2.0,
+        )
+    }
+}
+
+impl Constraint for Dot {
+    fn separates(&self, a: Pos, b: Pos) -> bool {
+        self.links(a, b) && a != b
+    }
+
+    fn prune(&self, size: Size, digits: &[u8], candidates: &mut [u32]) {
+        let n = size.digits();
+        let mask = |(row, col): Pos, candidates: &[u32]| match digits[row * n + col] {
+            0 => candidates[row * n + col],
+            digit => 1 << digit,
+        };
+        for (this, other) in [
+            (self.cells[0], self.cells[1]),
+            (self.cells[1], self.cells[0]),
+        ] {
+            let others = mask(other, candidates);
+            let allowed = (1..=n as u32)
+                .filter(|&digit| {
+                    (1..=n as u32).any(|o| others & 1 << o != 0 && self.allows(digit, o))
+                })
+                .fold(0, |allowed, digit| allowed | 1 << digit);
+            let (row, col) = this;
+            if digits[row * n + col] == 0 {
+                candidates[row * n + col] &= allowed;
+            }
+        }
+    }
+
+    fn violations(&self, size: Size, digits: &[u8]) -> Vec<Pos> {
+        let [a, b] = self.cells.map(|pos| u32::from(digit_at(size, digits, pos)));
+        if a == 0 || b == 0 || self.allows(a, b) {
+            Vec::new()
+        } else {
+            self.cells.to_vec()
+        }
+    }
+
+    fn links(&self, a: Pos, b: Pos) -> bool {
+        self.cells.contains(&a) && self.cells.contains(&b)
+    }
+}
+
+impl FromStr for Thermometer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self {
+            cells: parse_line(s)?,
+        })
+    }
+}
+
+impl Display for Thermometer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", variant::format_cells(&self.cells))
+    }
+}
+
+impl FromStr for Arrow {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self {
+            cells: parse_line(s)?,
+        })
+    }
+}
+
+impl Display for Arrow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", variant::format_cells(&self.cells))
+    }
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", variant::format_cells(&self.cells))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const ALL: u32 = 0b11_1111_1110;
+
+    fn board(placed: &[(Pos, u8)]) -> Vec<u8> {
+        let mut digits = vec![0; 81];
+        for &((row, col), digit) in placed {
+            digits[row * 9 + col] = digit;
+        }
+        digits
+    }
+
+    #[test]
+    fn test_thermometer() -> Result<()> {
+        let thermo = Thermometer::from_str("111213")?;
+        let mut candidates = vec![ALL; 81];
+        thermo.prune(Size::Nine, &board(&[]), &mut candidates);
+        assert_eq!(candidates[0], 0b1111_1110);
+        assert_eq!(candidates[1], 0b1_1111_1100);
+        assert_eq!(candidates[2], 0b11_1111_1000);
+
+        let digits = board(&[((0, 1), 5)]);
+        let mut candidates = vec![ALL; 81];
+        thermo.prune(Size::Nine, &digits, &mut candidates);
+        assert_eq!(candidates[0], 0b1_1110);
+        assert_eq!(candidates[2], 0b11_1100_0000);
+
+        assert!(thermo.violations(Size::Nine, &digits).is_empty());
+        let digits = board(&[((0, 0), 5), ((0, 2), 6)]);
+        assert_eq!(thermo.violations(Size::Nine, &digits), vec![(0, 0), (0, 2)]);
+        let digits = board(&[((0, 1), 9)]);
+        assert_eq!(thermo.violations(Size::Nine, &digits), vec![(0, 1)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arrow() -> Result<()> {
+        let arrow = Arrow::from_str("111213")?;
+        let mut candidates = vec![ALL; 81];
+        arrow.prune(Size::Nine, &board(&[]), &mut candidates);
+        assert_eq!(candidates[0], 0b11_1111_1100);
+        assert_eq!(candidates[1], 0b1_1111_1110);
+
+        let digits = board(&[((0, 0), 4)]);
+        let mut candidates = vec![ALL; 81];
+        arrow.prune(Size::Nine, &digits, &mut candidates);
+        assert_eq!(candidates[1], 0b1110);
+
+        assert!(arrow.violations(Size::Nine, &digits).is_empty());
+        let digits = board(&[((0, 0), 4), ((0, 1), 4)]);
+        assert_eq!(arrow.violations(Size::Nine, &digits), vec![(0, 0), (0, 1)]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(DotKind::White, 4, 0b10_1000)]
+    #[case(DotKind::Black, 4, 0b1_0000_0100)]
+    #[case(DotKind::Black, 5, 0)]
+    fn test_dot(#[case] kind: DotKind, #[case] digit: u8, #[case] expected: u32) -> Result<()> {
+        let dot = Dot::parse(kind, "1121")?;
+        let digits = board(&[((0, 0), digit)]);
+        let mut candidates = vec![ALL; 81];
+        dot.prune(Size::Nine, &digits, &mut candidates);
+        assert_eq!(candidates[9], expected);
+        assert!(dot.separates((0, 0), (1, 0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_lines() {
+        assert!(Thermometer::from_str("11").is_err());
+        assert!(Thermometer::from_str("1113").is_err());
+        assert!(Arrow::from_str("1122").is_ok());
+        assert!(Dot::parse(DotKind::White, "1122").is_err());
+        assert!(Dot::parse(DotKind::White, "111213").is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+use crate::{
+    backtrack,
+    benchmark::{run_suite, Timing},
+    generator::{get_random_sudoku, Difficulty},
+    samurai::Samurai,
+    sudoku_data::SudokuData,
+    techniques::{
+        find_solution, mark_against_solution, update_from_digits, update_from_digits_animated,
+        Duration,
+    },
+    trace::{Replay, Trace, MAX_STEPS},
+    variant::Variant,
+    Error, Result,
+};
+
+pub const READY: &str = "ready";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Request {
+    pub id: u32,
+    pub operation: Operation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Operation {
+    Solve {
+        digits: Vec<u8>,
+        givens: Vec<u8>,
+        variant: Variant,
+    },
+    Verify {
+        givens: Vec<u8>,
+        variant: Variant,
+    },
+    Generate(Difficulty),
+    Benchmark,
+    Trace {
+        givens: Vec<u8>,
+        variant: Variant,
+    },
+    SolveSamurai {
+        givens: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Response {
+    pub id: u32,
+    pub outcome: Outcome,
+    pub elapsed: std::time::Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Solved(Vec<u8>),
+    Checked(Vec<u8>),
+    Generated {
+        givens: String,
+        variant: Variant,
+    },
+    Benchmarked(Vec<Timing>),
+    Traced(Trace),
+    Failed {
+        error: String,
+        solution: Option<Vec<u8>>,
+    },
+}
+
+impl Operation {
+    #[must_use]
+    pub fn solve(sudoku: &SudokuData) -> Self {
+        Self::Solve {
+            digits: sudoku.digits(),
+            givens: sudoku.fixed_digits(),
+            variant: sudoku.variant.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn verify(sudoku: &SudokuData) -> Self {
+        Self::Verify {
+            givens: sudoku.fixed_digits(),
+            variant: sudoku.variant.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn trace(sudoku: &SudokuData) -> Self {
+        Self::Trace {
+            givens: sudoku.fixed_digits(),
+            variant: sudoku.variant.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn solve_samurai(samurai: &Samurai) -> Self {
+        Self::SolveSamurai {
+            givens: samurai.fixed_digits(),
+        }
+    }
+
+    fn run(self) -> Outcome {
+        match self {
+            Self::Solve {
+                digits,
+                givens,
+                variant,
+            } => match find_solution(&digits, &variant) {
+                Ok(solution) => Outcome::Solved(solution),
+                Err(err) => match find_solution(&givens, &variant) {
+                    Ok(solution) => Outcome::failed(&err, Some(solution)),
+                    Err(err) => Outcome::failed(&err, None),
+                },
+            },
+            Self::Verify { givens, variant } => find_solution(&givens, &variant)
+                .map_or_else(|err| Outcome::failed(&err, None), Outcome::Checked),
+            Self::Generate(difficulty) => get_random_sudoku(difficulty).map_or_else(
+                |err| Outcome::failed(&err, None),
+                |(givens, variant)| Outcome::Generated { givens, variant },
+            ),
+            Self::Benchmark => {
+                run_suite().map_or_else(|err| Outcome::failed(&err, None), Outcome::Benchmarked)
+            }
+            Self::Trace { givens, variant } => {
+                Outcome::Traced(backtrack::trace(&givens, &variant, MAX_STEPS))
+            }
+            Self::SolveSamurai { givens } => Samurai::with_givens(&givens)
+                .solution()
+                .map_or_else(|err| Outcome::failed(&err, None), Outcome::Solved),
+        }
+    }
+}
+
+impl Outcome {
+    fn failed(err: &Error, solution: Option<Vec<u8>>) -> Self {
+        Self::Failed {
+            error: err.to_string(),
+            solution,
+        }
+    }
+}
+
+#[must_use]
+pub fn handle(request: Request) -> Response {
+    let now = Instant::now();
+    let outcome = request.operation.run();
+    Response {
+        id: request.id,
+        outcome,
+        elapsed: now.elapsed(),
+    }
+}
+
+impl Response {
+    /// # Errors
+    ///
+    /// Fails if the operation failed, after marking the placed digits that
+    /// are wrong if the givens could be solved. Generated Samurai puzzles
+    /// do not fit on a single board.
+    pub fn apply(self, sudoku: &mut SudokuData) -> Result<String> {
+        let elapsed = Duration(self.elapsed);
+        match self.outcome {
+            Outcome::Solved(solution) => {
+                update_from_digits_animated(sudoku, &solution);
+                Ok(format!("Sudoku solved in {elapsed}"))
+            }
+            Outcome::Checked(solution) => {
+                mark_against_solution(sudoku, &solution);
+                Ok(format!("Sudoku verified in {elapsed}"))
+            }
+            Outcome::Generated { givens, variant } => {
+                let digits = variant.size.parse(&givens).ok_or(Error::UnsupportedSize)?;
+                *sudoku = SudokuData::new(variant);
+                update_from_digits(sudoku, &digits, true);
+                Ok(format!("Sudoku generated in {elapsed}"))
+            }
+            Outcome::Benchmarked(_) => Ok(format!("Benchmarks run in {elapsed}")),
+            Outcome::Traced(trace) => {
+                let steps = trace.steps.len();
+                *sudoku = Replay::new(trace).board();
+                Ok(format!("{steps} steps recorded in {elapsed}"))
+            }
+            Outcome::Failed { error, solution } => {
+                if let Some(solution) = solution {
+                    mark_against_solution(sudoku, &solution);
+                }
+                Err(Error::Worker(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        benchmark::load, generator::SAMURAI_SUDOKUS, sudoku_data::Cell, test_support::EASY_SUDOKU,
+    };
+
+    use super::*;
+
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    fn round_trip(operation: Operation) -> Result<Response> {
+        let request = Request { id: 7, operation };
+        let json = serde_json::to_string(&request)?;
+        let json = serde_json::to_string(&handle(serde_json::from_str(&json)?))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    #[test]
+    fn test_solve() -> Result<()> {
+        let mut sudoku = load(EASY_SUDOKU)?;
+        let response = round_trip(Operation::solve(&sudoku))?;
+        assert_eq!(response.id, 7);
+        assert!(response.apply(&mut sudoku)?.starts_with("Sudoku solved"));
+        assert!(sudoku.digits().iter().all(|&digit| digit != 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_marks_wrong_digits() -> Result<()> {
+        let mut sudoku = load(EASY_SUDOKU)?;
+        // the solution has 4 in the first cell, so 5 leaves no solution
+        sudoku.set(0, 0, 5, false);
+        let response = round_trip(Operation::solve(&sudoku))?;
+        assert!(matches!(response.apply(&mut sudoku), Err(Error::Worker(_))));
+        assert!(matches!(sudoku.get(0, 0), Cell::Error { value: 5, .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate() -> Result<()> {
+        let mut sudoku = SudokuData::default();
+        let response = round_trip(Operation::Generate(Difficulty::Diagonal))?;
+        assert!(matches!(response.outcome, Outcome::Generated { .. }));
+        response.apply(&mut sudoku)?;
+        assert!(sudoku.variant.diagonal);
+        assert!(sudoku.fixed_digits().iter().any(|&digit| digit != 0));
+        let samurai = round_trip(Operation::Generate(Difficulty::Samurai))?;
+        assert!(matches!(
+            samurai.apply(&mut sudoku),
+            Err(Error::UnsupportedSize)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_samurai() -> Result<()> {
+        let mut samurai = Samurai::from_str(SAMURAI_SUDOKUS[0])?;
+        let response = round_trip(Operation::solve_samurai(&samurai))?;
+        let Outcome::Solved(solution) = response.outcome else {
+            return Err("samurai not solved".into());
+        };
+        samurai.fill(&solution);
+        assert!(samurai.digits().iter().all(|&digit| digit != 0));
+        assert_eq!(
+            samurai.fixed_digits(),
+            Samurai::from_str(SAMURAI_SUDOKUS[0])?.fixed_digits()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{sudoku_data::SudokuData, variant::Variant, Error, Result};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub givens: Vec<u8>,
+    pub variant: Variant,
+}
+
+impl From<&SudokuData> for Puzzle {
+    fn from(sudoku: &SudokuData) -> Self {
+        Self {
+            givens: sudoku.fixed_digits(),
+            variant: sudoku.variant.clone(),
+        }
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (givens, variant) = s.split_once(':').unwrap_or((s, ""));
+        let variant = Variant::from_str(variant)?;
+        let givens = variant
+            .size
+            .parse(givens.trim())
+            .ok_or(Error::ParsePuzzle)?;
+        Ok(Self { givens, variant })
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.variant.size.format(&self.givens))?;
+        if self.variant == Variant::default() {
+            Ok(())
+        } else {
+            write!(f, ":{}", self.variant)
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use crate::test_support::EASY_SUDOKU;
+
+    use super::*;
+
+    #[test]
+    fn test_puzzle_roundtrip() -> Result<()> {
+        let classic = Puzzle::from_str(EASY_SUDOKU)?;
+        assert_eq!(classic.variant, Variant::default());
+        assert_eq!(classic.givens[2], 3);
+        assert_eq!(classic.to_string(), EASY_SUDOKU);
+
+        let input = format!("{EASY_SUDOKU}:t112131_a4142_b5152");
+        let puzzle = Puzzle::from_str(&input)?;
+        assert_eq!(puzzle.variant.thermos.len(), 1);
+        assert_eq!(puzzle.variant.arrows.len(), 1);
+        assert_eq!(puzzle.variant.dots.len(), 1);
+        assert_eq!(puzzle.to_string(), input);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_puzzle() {
+        assert!(Puzzle::from_str("123").is_err());
+        assert!(Puzzle::from_str(&format!("{EASY_SUDOKU}:s4")).is_err());
+        assert!(Puzzle::from_str(&format!("{EASY_SUDOKU}:t11")).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::Display;
+
+use crate::{
+    auto::{apply_up_to, Level},
+    encoding::to_sudoku,
+    sudoku_data::SudokuData,
+    techniques::update_from_digits,
+    variant::Variant,
+    Result,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rating {
+    VisibleSingles,
+    HiddenSingles,
+    Doubles,
+    Triples,
+    Fish,
+    Backtracking,
+}
+
+impl Rating {
+    #[must_use]
+    pub fn of(action: &str) -> Self {
+        match action {
+            "SINGLES" | "CONSTRAINTS" => Self::VisibleSingles,
+            "HIDDEN" => Self::HiddenSingles,
+            "DOUBLES" => Self::Doubles,
+            "TRIPLES" => Self::Triples,
+            "X-WING" => Self::Fish,
+            _ => Self::Backtracking,
+        }
+    }
+}
+
+/// # Errors
+///
+/// Fails on boards that the classic techniques do not apply to, see
+/// [`Variant::check_classic_techniques`], and on boards that contradict
+/// themselves.
+pub fn rate(givens: &[u8], variant: &Variant) -> Result<Rating> {
+    variant.check_classic_techniques()?;
+    rust_sudoku_solver::check_constraints(&mut to_sudoku(givens))?;
+    let mut sudoku = SudokuData::new(variant.clone());
+    update_from_digits(&mut sudoku, givens, true);
+    let report = apply_up_to(&mut sudoku, Level::All)?;
+    if !report.solved {
+        return Ok(Rating::Backtracking);
+    }
+    Ok(report
+        .counts
+        .iter()
+        .filter(|&&(_, count)| count > 0)
+        .map(|&(action, _)| Rating::of(action))
+        .max()
+        .unwrap_or(Rating::VisibleSingles))
+}
+
+impl Display for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::VisibleSingles => "visible singles",
+            Self::HiddenSingles => "hidden singles",
+            Self::Doubles => "doubles",
+            Self::Triples => "triples",
+            Self::Fish => "fish",
+            Self::Backtracking => "backtracking",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        generator::HARD_SUDOKUS,
+        size::Size,
+        test_support::{EASY_SUDOKU, FISH_SUDOKU, PAIRS_SUDOKU},
+        Error,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_rate_easy_sudoku() -> Result<()> {
+        let givens = Size::Nine.parse(EASY_SUDOKU).ok_or(Error::ParsePuzzle)?;
+        let rating = rate(&givens, &Variant::default())?;
+        assert!(rating <= Rating::HiddenSingles);
+        let solution = crate::backtrack::solve(&givens, &Variant::default())?;
+        assert_eq!(
+            rate(&solution, &Variant::default())?,
+            Rating::VisibleSingles
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_pairs_and_triples() -> Result<()> {
+        let pairs = Size::Nine.parse(PAIRS_SUDOKU).ok_or(Error::ParsePuzzle)?;
+        assert_eq!(rate(&pairs, &Variant::default())?, Rating::Doubles);
+        // the singles and a naked triple solve this one, without any pairs
+        let triples = Size::Nine
+            .parse(HARD_SUDOKUS[13])
+            .ok_or(Error::ParsePuzzle)?;
+        assert_eq!(rate(&triples, &Variant::default())?, Rating::Triples);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_fish() -> Result<()> {
+        let fish = Size::Nine.parse(FISH_SUDOKU).ok_or(Error::ParsePuzzle)?;
+        assert_eq!(rate(&fish, &Variant::default())?, Rating::Fish);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_unsupported_size() -> Result<()> {
+        let variant = Variant::from_str("s4")?;
+        assert!(matches!(
+            rate(&[0; 16], &variant),
+            Err(Error::UnsupportedSize)
+        ));
+        Ok(())
+    }
+}
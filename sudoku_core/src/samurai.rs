@@ -0,0 +1,241 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    backtrack,
+    constraint::Pos,
+    size::Size,
+    sudoku_data::{Cell, SudokuData},
+    variant::Variant,
+    Error, Result,
+};
+
+pub const SIDE: usize = 21;
+
+pub const ORIGINS: [Pos; 5] = [(0, 0), (0, 12), (6, 6), (12, 0), (12, 12)];
+
+#[derive(Debug, Clone)]
+pub struct Samurai {
+    pub grids: Vec<SudokuData>,
+}
+
+#[must_use]
+pub fn grids_at(row: usize, col: usize) -> Vec<(usize, Pos)> {
+    ORIGINS
+        .iter()
+        .enumerate()
+        .filter_map(|(grid, &(top, left))| {
+            let (r, c) = (row.checked_sub(top)?, col.checked_sub(left)?);
+            (r < 9 && c < 9).then_some((grid, (r, c)))
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn cells() -> Vec<Pos> {
+    (0..SIDE)
+        .flat_map(|row| (0..SIDE).map(move |col| (row, col)))
+        .filter(|&(row, col)| !grids_at(row, col).is_empty())
+        .collect()
+}
+
+impl Default for Samurai {
+    fn default() -> Self {
+        Self {
+            grids: vec![SudokuData::default(); ORIGINS.len()],
+        }
+    }
+}
+
+impl Samurai {
+    #[must_use]
+    pub fn with_givens(givens: &[u8]) -> Self {
+        let mut samurai = Self::default();
+        for (&(row, col), &digit) in cells().iter().zip(givens) {
+            if digit != 0 {
+                samurai.set(row, col, digit, true);
+            }
+        }
+        samurai
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> Option<Cell> {
+        let grids = grids_at(row, col);
+        let (&(first, (r, c)), others) = grids.split_first()?;
+        match self.grids[first].get(r, c) {
+            Cell::Empty { mut choices } => {
+                for &(grid, (r, c)) in others {
+                    if let Cell::Empty { choices: other } = self.grids[grid].get(r, c) {
+                        for (choice, other) in choices.iter_mut().zip(other) {
+                            *choice &= other;
+                        }
+                    }
+                }
+                Some(Cell::Empty { choices })
+            }
+            cell => Some(cell),
+        }
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: u8, fixed: bool) {
+        for (grid, (r, c)) in grids_at(row, col) {
+            self.grids[grid].set(r, c, value, fixed);
+        }
+    }
+
+    pub fn unset(&mut self, row: usize, col: usize) {
+        for (grid, (r, c)) in grids_at(row, col) {
+            self.grids[grid].unset(r, c);
+        }
+    }
+
+    #[must_use]
+    pub fn digits(&self) -> Vec<u8> {
+        cells()
+            .into_iter()
+            .map(|(row, col)| {
+                self.get(row, col)
+                    .and_then(|cell| cell.value())
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn fixed_digits(&self) -> Vec<u8> {
+        cells()
+            .into_iter()
+            .map(|(row, col)| match self.get(row, col) {
+                Some(Cell::FixedValue { value }) => value,
+                _ => 0,
+            })
+            .collect()
+    }
+
+    /// # Errors
+    ///
+    /// Fails with [`Error::NoSolution`] if the givens can not be completed.
+    pub fn solution(&self) -> Result<Vec<u8>> {
+        let cells = cells();
+        let variant = Variant::default();
+        let peers = cells
+            .iter()
+            .map(|&(row, col)| {
+                let mut peers: Vec<_> = grids_at(row, col)
+                    .into_iter()
+                    .flat_map(|(grid, (r, c))| {
+                        let (top, left) = ORIGINS[grid];
+                        variant
+                            .peers(r, c)
+                            .into_iter()
+                            .map(move |(pr, pc)| (top + pr, left + pc))
+                    })
+                    .filter_map(|pos| cells.binary_search(&pos).ok())
+                    .collect();
+                peers.sort_unstable();
+                peers.dedup();
+                peers
+            })
+            .collect();
+        backtrack::find_solutions_with_peers(&self.fixed_digits(), Size::Nine, peers, 1)
+            .into_iter()
+            .next()
+            .ok_or(Error::NoSolution)
+    }
+
+    /// # Errors
+    ///
+    /// Fails like [`Samurai::solution`], leaving the board as it was.
+    pub fn solve(&mut self) -> Result<()> {
+        let solution = self.solution()?;
+        self.fill(&solution);
+        Ok(())
+    }
+
+    pub fn fill(&mut self, solution: &[u8]) {
+        let mut solved = Self::with_givens(&self.fixed_digits());
+        for (&(row, col), &digit) in cells().iter().zip(solution) {
+            solved.set(row, col, digit, false);
+        }
+        *self = solved;
+    }
+}
+
+impl FromStr for Samurai {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let givens = s
+            .chars()
+            .map(|c| match c {
+                '.' => Some(0),
+                c => Size::Nine.parse_digit(c),
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::ParsePuzzle)?;
+        if givens.len() != cells().len() {
+            return Err(Error::ParsePuzzle);
+        }
+        Ok(Self::with_givens(&givens))
+    }
+}
+
+impl Display for Samurai {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Size::Nine.format(&self.digits()))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use crate::generator::SAMURAI_SUDOKUS;
+
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(cells().len(), 369);
+        assert_eq!(grids_at(6, 6), vec![(0, (6, 6)), (2, (0, 0))]);
+        assert_eq!(grids_at(14, 18), vec![(4, (2, 6))]);
+        assert!(grids_at(0, 9).is_empty());
+        assert!(grids_at(10, 4).is_empty());
+    }
+
+    #[test]
+    fn test_shared_candidates() {
+        let mut samurai = Samurai::default();
+        samurai.set(0, 6, 5, false);
+        // the column of the top left grid reaches into the centre grid
+        assert!(matches!(
+            samurai.get(8, 6),
+            Some(Cell::Empty { choices }) if !choices[4]
+        ));
+        // while the centre grid on its own still allows it
+        assert!(matches!(
+            samurai.grids[2].get(2, 0),
+            Cell::Empty { choices } if choices[4]
+        ));
+        assert!(matches!(
+            samurai.get(9, 6),
+            Some(Cell::Empty { choices }) if choices[4]
+        ));
+
+        samurai.set(8, 8, 7, false);
+        assert_eq!(samurai.grids[2].get(2, 2).value(), Some(7));
+        samurai.unset(8, 8);
+        assert!(samurai.grids[2].get(2, 2).is_empty());
+    }
+
+    #[test]
+    fn test_solve_samurai() -> Result<()> {
+        let mut samurai = Samurai::from_str(SAMURAI_SUDOKUS[0])?;
+        assert_eq!(samurai.to_string(), SAMURAI_SUDOKUS[0]);
+        samurai.solve()?;
+        assert!(samurai.digits().iter().all(|&digit| digit != 0));
+        for grid in &samurai.grids {
+            assert!(grid.variant.conflicts(&grid.digits()).is_empty());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Size {
+    Four,
+    Six,
+    #[default]
+    Nine,
+    Sixteen,
+}
+
+pub const MAX_DIGITS: usize = 16;
+
+impl Size {
+    pub const ALL: [Self; 4] = [Self::Four, Self::Six, Self::Nine, Self::Sixteen];
+
+    #[must_use]
+    pub const fn digits(self) -> usize {
+        match self {
+            Self::Four => 4,
+            Self::Six => 6,
+            Self::Nine => 9,
+            Self::Sixteen => 16,
+        }
+    }
+
+    #[must_use]
+    pub const fn cells(self) -> usize {
+        self.digits() * self.digits()
+    }
+
+    #[must_use]
+    pub const fn box_rows(self) -> usize {
+        match self {
+            Self::Four | Self::Six => 2,
+            Self::Nine => 3,
+            Self::Sixteen => 4,
+        }
+    }
+
+    #[must_use]
+    pub const fn box_cols(self) -> usize {
+        self.digits() / self.box_rows()
+    }
+
+    #[must_use]
+    pub fn all_digits(self) -> [bool; MAX_DIGITS] {
+        let mut digits = [false; MAX_DIGITS];
+        digits[..self.digits()].fill(true);
+        digits
+    }
+
+    #[must_use]
+    pub fn digit_char(self, digit: u8) -> char {
+        let (value, radix) = match self {
+            Self::Sixteen => (u32::from(digit) - 1, 16),
+            Self::Four | Self::Six | Self::Nine => (u32::from(digit), 10),
+        };
+        char::from_digit(value, radix).map_or('?', |c| c.to_ascii_uppercase())
+    }
+
+    #[must_use]
+    pub fn parse_digit(self, c: char) -> Option<u8> {
+        let digit = match self {
+            Self::Sixteen => c.to_digit(16)? + 1,
+            Self::Four | Self::Six | Self::Nine => c.to_digit(10)?,
+        };
+        (1..=self.digits() as u32)
+            .contains(&digit)
+            .then_some(digit as u8)
+    }
+
+    #[must_use]
+    pub fn format(self, digits: &[u8]) -> String {
+        digits
+            .iter()
+            .map(|&digit| {
+                if digit == 0 {
+                    '.'
+                } else {
+                    self.digit_char(digit)
+                }
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn parse(self, s: &str) -> Option<Vec<u8>> {
+        let digits = s
+            .chars()
+            .map(|c| {
+                if c == '.' {
+                    Some(0)
+                } else {
+                    self.parse_digit(c)
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (digits.len() == self.cells()).then_some(digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Size::Four, 2, 2)]
+    #[case(Size::Six, 2, 3)]
+    #[case(Size::Nine, 3, 3)]
+    #[case(Size::Sixteen, 4, 4)]
+    fn test_box_dimensions(#[case] size: Size, #[case] rows: usize, #[case] cols: usize) {
+        assert_eq!((size.box_rows(), size.box_cols()), (rows, cols));
+    }
+
+    #[test]
+    fn test_hex_digits() {
+        assert_eq!(Size::Sixteen.digit_char(1), '0');
+        assert_eq!(Size::Sixteen.digit_char(16), 'F');
+        assert_eq!(Size::Sixteen.parse_digit('f'), Some(16));
+        assert_eq!(Size::Nine.parse_digit('0'), None);
+        assert_eq!(Size::Six.parse_digit('7'), None);
+    }
+
+    #[test]
+    fn test_format_roundtrip() {
+        let board = "1..2.3..4.....21";
+        assert_eq!(
+            Size::Four.parse(board).map(|d| Size::Four.format(&d)),
+            Some(board.into())
+        );
+        assert_eq!(Size::Four.parse("1..2"), None);
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::{diff::Changes, sudoku_data::SudokuData, techniques, Result};
+
+pub const START: &str = "START";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathEntry {
+    pub technique: &'static str,
+    pub changes: Changes,
+    pub elapsed: Duration,
+    board: SudokuData,
+}
+
+impl PathEntry {
+    #[must_use]
+    pub fn new(
+        technique: &'static str,
+        before: &SudokuData,
+        after: &SudokuData,
+        elapsed: Duration,
+    ) -> Self {
+        Self {
+            technique,
+            changes: Changes::between(before, after),
+            elapsed,
+            board: after.clone(),
+        }
+    }
+
+    #[must_use]
+    pub const fn board(&self) -> &SudokuData {
+        &self.board
+    }
+}
+
+impl Display for PathEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = self.board.size();
+        let placed = self.changes.placed.iter().map(|&((row, col), digit)| {
+            format!("r{}c{}={}", row + 1, col + 1, size.digit_char(digit))
+        });
+        let eliminated = self.changes.eliminated.iter().map(|&((row, col), digit)| {
+            format!("r{}c{}≠{}", row + 1, col + 1, size.digit_char(digit))
+        });
+        write!(
+            f,
+            "{} ({})",
+            self.technique,
+            techniques::Duration(self.elapsed)
+        )?;
+        for change in placed.chain(eliminated) {
+            write!(f, " {change}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SolutionPath {
+    entries: Vec<PathEntry>,
+    current: usize,
+}
+
+impl SolutionPath {
+    /// # Errors
+    ///
+    /// Fails like the technique.
+    pub fn apply(
+        &mut self,
+        technique: &'static str,
+        sudoku: &mut SudokuData,
+        f: impl FnOnce(&mut SudokuData) -> Result<String>,
+    ) -> Result<String> {
+        let before = sudoku.clone();
+        let now = Instant::now();
+        let result = f(sudoku);
+        if result.is_ok() {
+            self.record(technique, &before, sudoku, now.elapsed());
+        }
+        result
+    }
+
+    pub fn record(
+        &mut self,
+        technique: &'static str,
+        before: &SudokuData,
+        after: &SudokuData,
+        elapsed: Duration,
+    ) {
+        if self.entries.is_empty() {
+            self.entries
+                .push(PathEntry::new(START, before, before, Duration::ZERO));
+        }
+        self.entries.truncate(self.current + 1);
+        self.entries
+            .push(PathEntry::new(technique, before, after, elapsed));
+        self.current = self.entries.len() - 1;
+    }
+
+    pub fn rewind(&mut self, index: usize) -> Option<SudokuData> {
+        let board = self.entries.get(index)?.board.clone();
+        self.current = index;
+        Some(board)
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[PathEntry] {
+        &self.entries
+    }
+
+    #[must_use]
+    pub const fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use crate::{
+        benchmark::load,
+        techniques::{check_constraints, place_all_visible_singles},
+        test_support::EASY_SUDOKU,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_record_changes() -> Result<()> {
+        let mut sudoku = load(EASY_SUDOKU)?;
+        let mut path = SolutionPath::default();
+        path.apply("SINGLES", &mut sudoku, place_all_visible_singles)?;
+        let [start, singles] = path.entries() else {
+            return Err(crate::Error::NoSolution);
+        };
+        assert_eq!(start.technique, START);
+        assert!(start.changes.is_empty());
+        assert!(!singles.changes.placed.is_empty());
+        for &((row, col), digit) in &singles.changes.placed {
+            assert_eq!(sudoku.get(row, col).value(), Some(digit));
+        }
+        assert!(singles.to_string().starts_with("SINGLES ("));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rewind() -> Result<()> {
+        let mut sudoku = load(EASY_SUDOKU)?;
+        let start = sudoku.digits();
+        let mut path = SolutionPath::default();
+        path.apply("SINGLES", &mut sudoku, place_all_visible_singles)?;
+        path.apply("SINGLES", &mut sudoku, place_all_visible_singles)?;
+        assert_eq!(path.entries().len(), 3);
+
+        let board = path.rewind(0).ok_or(crate::Error::NoSolution)?;
+        assert_eq!(board.digits(), start);
+        assert_eq!(path.current(), 0);
+        assert!(path.rewind(3).is_none());
+
+        // applying a technique after rewinding replaces what came after
+        sudoku = board;
+        path.apply("CONSTRAINTS", &mut sudoku, check_constraints)?;
+        let techniques: Vec<_> = path.entries().iter().map(|e| e.technique).collect();
+        assert_eq!(techniques, [START, "CONSTRAINTS"]);
+        Ok(())
+    }
+}
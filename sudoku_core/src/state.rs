@@ -0,0 +1,361 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    auto::Level,
+    diff::Changes,
+    size::Size,
+    sudoku_data::{Color, SudokuData},
+    Error, Result,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DigitMode {
+    #[default]
+    Value,
+    Choice,
+    Corner,
+    Centre,
+}
+
+impl DigitMode {
+    pub const ALL: [Self; 4] = [Self::Value, Self::Choice, Self::Corner, Self::Centre];
+
+    pub fn toggle(&mut self) {
+        *self = match self {
+            Self::Value => Self::Choice,
+            Self::Choice => Self::Corner,
+            Self::Corner => Self::Centre,
+            Self::Centre => Self::Value,
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Value => "DIGITS",
+            Self::Choice => "CHOICES",
+            Self::Corner => "CORNER",
+            Self::Centre => "CENTRE",
+        }
+    }
+
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::Value => 0,
+            Self::Choice => 1,
+            Self::Corner => 2,
+            Self::Centre => 3,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GameState {
+    pub active_cell: Option<(usize, usize)>,
+    #[serde(default)]
+    pub selection: Vec<(usize, usize)>,
+    #[serde(skip)]
+    pub dragging: bool,
+    pub message: Option<String>,
+    pub dark_mode: DarkMode,
+    #[serde(default)]
+    pub highlights: Highlights,
+    #[serde(default)]
+    pub color: Color,
+    #[serde(default)]
+    pub level: Level,
+    #[serde(default)]
+    pub tally: Tally,
+    #[serde(skip)]
+    pub changes: Changes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Active,
+    Selected,
+    SameDigit,
+    Peer,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightKind {
+    Peers,
+    SameDigit,
+    Candidates,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlights {
+    pub peers: bool,
+    pub same_digit: bool,
+    pub candidates: bool,
+}
+
+impl Default for Highlights {
+    fn default() -> Self {
+        Self {
+            peers: true,
+            same_digit: true,
+            candidates: true,
+        }
+    }
+}
+
+impl Highlights {
+    #[must_use]
+    pub const fn get(self, kind: HighlightKind) -> bool {
+        match kind {
+            HighlightKind::Peers => self.peers,
+            HighlightKind::SameDigit => self.same_digit,
+            HighlightKind::Candidates => self.candidates,
+        }
+    }
+
+    pub fn toggle(&mut self, kind: HighlightKind) {
+        let setting = match kind {
+            HighlightKind::Peers => &mut self.peers,
+            HighlightKind::SameDigit => &mut self.same_digit,
+            HighlightKind::Candidates => &mut self.candidates,
+        };
+        *setting = !*setting;
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tally {
+    pub hints: usize,
+    pub mistakes: usize,
+}
+
+impl Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hints, {} mistakes", self.hints, self.mistakes)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub enum DarkMode {
+    Light,
+    #[default]
+    Dark,
+}
+
+impl DarkMode {
+    #[must_use]
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        *self = match self {
+            Self::Light => Self::Dark,
+            Self::Dark => Self::Light,
+        }
+    }
+
+    #[must_use]
+    pub const fn active(self) -> bool {
+        matches!(self, Self::Dark)
+    }
+}
+
+impl FromStr for GameState {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut game_state = Self::default();
+        let mut chars = s.chars();
+        if let Some(c) = chars.next() {
+            match c {
+                'l' => game_state.dark_mode = DarkMode::Light,
+                'd' => game_state.dark_mode = DarkMode::Dark,
+                _ => return Err(Error::GenerateSudoku),
+            }
+        }
+        Ok(game_state)
+    }
+}
+
+impl GameState {
+    pub fn show_result(&mut self, result: Result<impl Display>) {
+        match result {
+            Ok(v) => self.message = Some(v.to_string()),
+            Err(e) => self.message = Some(e.to_string()),
+        }
+    }
+
+    pub fn show_changes(&mut self, result: Result<impl Display>, changes: Changes) {
+        match result {
+            Ok(v) => {
+                self.message = Some(format!("{v} ({changes})"));
+                self.changes = changes;
+            }
+            Err(e) => {
+                self.message = Some(e.to_string());
+                self.changes = Changes::default();
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn is_active_cell(&self, row: usize, col: usize) -> bool {
+        self.active_cell.is_some() && self.active_cell == Some((row, col))
+    }
+
+    #[must_use]
+    pub fn is_selected(&self, row: usize, col: usize) -> bool {
+        self.is_active_cell(row, col) || self.selection.contains(&(row, col))
+    }
+
+    #[must_use]
+    pub fn selected_cells(&self) -> Vec<(usize, usize)> {
+        if self.selection.is_empty() {
+            self.active_cell.into_iter().collect()
+        } else {
+            self.selection.clone()
+        }
+    }
+
+    pub fn select(&mut self, cell: (usize, usize)) {
+        self.active_cell = Some(cell);
+        self.selection = vec![cell];
+    }
+
+    pub fn extend_selection(&mut self, cell: (usize, usize)) {
+        if self.selection.is_empty() {
+            self.selection.extend(self.active_cell);
+        }
+        if !self.selection.contains(&cell) {
+            self.selection.push(cell);
+        }
+        self.active_cell = Some(cell);
+    }
+
+    pub fn toggle_selection(&mut self, cell: (usize, usize)) {
+        if self.selection.is_empty() {
+            self.selection.extend(self.active_cell);
+        }
+        if let Some(idx) = self.selection.iter().position(|&c| c == cell) {
+            self.selection.remove(idx);
+            self.active_cell = self.selection.last().copied();
+        } else {
+            self.selection.push(cell);
+            self.active_cell = Some(cell);
+        }
+    }
+
+    pub fn move_active(&mut self, size: Size, direction: (i32, i32), extend: bool) {
+        let Some((row, col)) = self.active_cell else {
+            return;
+        };
+        let new_row = row as i32 + direction.0;
+        let new_col = col as i32 + direction.1;
+        if is_valid_cell(size, new_row, new_col) {
+            let cell = (new_row as usize, new_col as usize);
+            if extend {
+                self.extend_selection(cell);
+            } else {
+                self.select(cell);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn highlighted_digit(&self, sudoku: &SudokuData) -> Option<u8> {
+        let (row, col) = self.active_cell?;
+        sudoku.get(row, col).value()
+    }
+
+    #[must_use]
+    pub fn highlight(&self, sudoku: &SudokuData, row: usize, col: usize) -> Highlight {
+        let Some(active) = self.active_cell else {
+            return Highlight::None;
+        };
+        let digit = self.highlighted_digit(sudoku);
+        if active == (row, col) {
+            Highlight::Active
+        } else if self.selection.contains(&(row, col)) {
+            Highlight::Selected
+        } else if self.highlights.same_digit
+            && digit.is_some()
+            && sudoku.get(row, col).value() == digit
+        {
+            Highlight::SameDigit
+        } else if self.highlights.peers && sudoku.are_peers(active, (row, col)) {
+            Highlight::Peer
+        } else {
+            Highlight::None
+        }
+    }
+
+    #[must_use]
+    pub fn is_highlighted_choice(&self, sudoku: &SudokuData, digit: u8) -> bool {
+        self.highlights.candidates && self.highlighted_digit(sudoku) == Some(digit)
+    }
+}
+
+fn is_valid_cell(size: Size, row: i32, col: i32) -> bool {
+    let cells = 0..size.digits() as i32;
+    cells.contains(&row) && cells.contains(&col)
+}
+
+impl Display for GameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.dark_mode {
+            DarkMode::Light => write!(f, "l"),
+            DarkMode::Dark => write!(f, "d"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extend_selection() {
+        let mut state = GameState {
+            active_cell: Some((0, 0)),
+            ..Default::default()
+        };
+        state.extend_selection((0, 1));
+        state.extend_selection((0, 1));
+        assert_eq!(state.selected_cells(), vec![(0, 0), (0, 1)]);
+        assert_eq!(state.active_cell, Some((0, 1)));
+
+        state.select((4, 4));
+        assert_eq!(state.selected_cells(), vec![(4, 4)]);
+    }
+
+    #[test]
+    fn test_move_active() {
+        let mut state = GameState::default();
+        state.select((0, 3));
+        state.move_active(Size::Four, (0, 1), false);
+        state.move_active(Size::Four, (0, 1), false);
+        assert_eq!(state.active_cell, Some((0, 3)));
+        state.move_active(Size::Four, (1, 0), true);
+        assert_eq!(state.selected_cells(), vec![(0, 3), (1, 3)]);
+    }
+
+    #[test]
+    fn test_toggle_selection() {
+        let mut state = GameState::default();
+        state.select((1, 1));
+        state.toggle_selection((2, 2));
+        assert!(state.is_selected(1, 1));
+        assert!(state.is_selected(2, 2));
+
+        state.toggle_selection((2, 2));
+        assert!(!state.is_selected(2, 2));
+        assert_eq!(state.active_cell, Some((1, 1)));
+    }
+}
@@ -0,0 +1,814 @@
+use crate::{
+    encoding::{compress_string, to_sudoku},
+    size::{Size, MAX_DIGITS},
+    techniques::{from_choices, to_choices},
+    variant::Variant,
+    Result,
+};
+use rust_sudoku_solver::Sudoku;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_compact::compact;
+use std::{fmt::Display, str::FromStr};
+
+#[compact]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedSudokuData")]
+pub struct SudokuData {
+    pub rows: Vec<SudokuRow>,
+    #[serde(default, skip_serializing_if = "Variant::is_classic")]
+    pub variant: Variant,
+}
+
+#[compact]
+#[derive(Deserialize)]
+struct UncheckedSudokuData {
+    rows: Vec<SudokuRow>,
+    #[serde(default)]
+    variant: Variant,
+}
+
+impl TryFrom<UncheckedSudokuData> for SudokuData {
+    type Error = crate::Error;
+
+    fn try_from(unchecked: UncheckedSudokuData) -> Result<Self> {
+        let n = unchecked.variant.size.digits();
+        let fits = |row: &SudokuRow| {
+            row.cells.len() == n
+                && row.marks.len() == n
+                && row
+                    .cells
+                    .iter()
+                    .filter_map(Cell::value)
+                    .all(|value| (1..=n).contains(&usize::from(value)))
+        };
+        if unchecked.rows.len() != n || !unchecked.rows.iter().all(fits) {
+            return Err(crate::Error::ParsePuzzle);
+        }
+        Ok(Self {
+            rows: unchecked.rows,
+            variant: unchecked.variant,
+        })
+    }
+}
+
+#[compact]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SudokuRow {
+    pub cells: Vec<Cell>,
+    #[serde(default = "default_marks")]
+    pub marks: Vec<CellMarks>,
+}
+
+/// Boards saved before marks existed were always 9×9.
+fn default_marks() -> Vec<CellMarks> {
+    vec![CellMarks::default(); 9]
+}
+
+#[compact]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellMarks {
+    #[serde(default, skip_serializing_if = "none_set")]
+    #[serde(serialize_with = "serialize_to_int")]
+    #[serde(deserialize_with = "deserialize_from_int")]
+    pub eliminated: [bool; MAX_DIGITS],
+    #[serde(default, skip_serializing_if = "none_set")]
+    #[serde(serialize_with = "serialize_to_int")]
+    #[serde(deserialize_with = "deserialize_from_int")]
+    pub corner: [bool; MAX_DIGITS],
+    #[serde(default, skip_serializing_if = "none_set")]
+    #[serde(serialize_with = "serialize_to_int")]
+    #[serde(deserialize_with = "deserialize_from_int")]
+    pub centre: [bool; MAX_DIGITS],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    #[serde(default, skip_serializing_if = "no_colors")]
+    pub choice_colors: [Option<Color>; MAX_DIGITS],
+}
+
+impl CellMarks {
+    #[must_use]
+    pub const fn pencil_marks(&self, layer: PencilMark) -> &[bool; MAX_DIGITS] {
+        match layer {
+            PencilMark::Corner => &self.corner,
+            PencilMark::Centre => &self.centre,
+        }
+    }
+
+    #[must_use]
+    pub fn has_pencil_marks(&self) -> bool {
+        !none_set(&self.corner) || !none_set(&self.centre)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PencilMark {
+    Corner,
+    Centre,
+}
+
+#[compact]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Color {
+    #[default]
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl Color {
+    #[must_use]
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Red => "bg-red-400",
+            Self::Orange => "bg-orange-400",
+            Self::Yellow => "bg-yellow-300",
+            Self::Green => "bg-green-400",
+            Self::Blue => "bg-sky-400",
+            Self::Purple => "bg-purple-400",
+        }
+    }
+}
+
+#[compact]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cell {
+    Empty {
+        #[serde(serialize_with = "serialize_to_int")]
+        #[serde(deserialize_with = "deserialize_from_int")]
+        choices: [bool; MAX_DIGITS],
+    },
+    Value {
+        value: u8,
+        #[serde(skip)]
+        choices: [bool; MAX_DIGITS],
+    },
+    FixedValue {
+        value: u8,
+    },
+    AnimatedValue {
+        value: u8,
+        #[serde(skip)]
+        choices: [bool; MAX_DIGITS],
+        #[serde(skip)]
+        fade_delay_ms: i32,
+        #[serde(skip)]
+        animation: String,
+    },
+    Error {
+        value: u8,
+        #[serde(skip)]
+        choices: [bool; MAX_DIGITS],
+    },
+}
+
+fn serialize_to_int<S>(
+    arr: &[bool; MAX_DIGITS],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u16(to_int(arr))
+}
+
+fn deserialize_from_int<'de, D>(
+    deserializer: D,
+) -> std::result::Result<[bool; MAX_DIGITS], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = u16::deserialize(deserializer)?;
+    Ok(from_int(value))
+}
+
+fn none_set(arr: &[bool; MAX_DIGITS]) -> bool {
+    !arr.iter().any(|&b| b)
+}
+
+fn no_colors(colors: &[Option<Color>; MAX_DIGITS]) -> bool {
+    colors.iter().all(Option::is_none)
+}
+
+fn to_int(arr: &[bool; MAX_DIGITS]) -> u16 {
+    let mut result = 0;
+    for (i, &b) in arr.iter().enumerate() {
+        if b {
+            result |= 1 << i;
+        }
+    }
+    result
+}
+
+fn from_int(value: u16) -> [bool; MAX_DIGITS] {
+    let mut result = [false; MAX_DIGITS];
+    (0..MAX_DIGITS).for_each(|i| {
+        result[i] = (value & (1 << i)) != 0;
+    });
+    result
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::empty(Size::default())
+    }
+}
+
+impl Cell {
+    #[must_use]
+    pub fn empty(size: Size) -> Self {
+        Self::Empty {
+            choices: size.all_digits(),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty { .. })
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> Option<u8> {
+        match self {
+            Self::Empty { .. } => None,
+            Self::Value { value, .. }
+            | Self::FixedValue { value }
+            | Self::AnimatedValue { value, .. }
+            | Self::Error { value, .. } => Some(*value),
+        }
+    }
+}
+
+impl From<&SudokuData> for Sudoku {
+    fn from(data: &SudokuData) -> Self {
+        let mut sudoku = to_sudoku(&data.digits());
+        // keep the candidates that earlier techniques eliminated
+        for (idx, cell) in data.rows.iter().flat_map(|row| &row.cells).enumerate() {
+            if let Cell::Empty { choices } = cell {
+                sudoku.bitboard[idx] &= from_choices(choices);
+            }
+        }
+        sudoku
+    }
+}
+
+impl From<&Sudoku> for SudokuData {
+    fn from(sudoku: &Sudoku) -> Self {
+        let mut data = Self::default();
+        for i in 0..9 {
+            for j in 0..9 {
+                let idx = 9 * i + j;
+                if sudoku.digits[idx] == 0 {
+                    data.rows[i].cells[j] = Cell::Empty {
+                        choices: to_choices(sudoku.bitboard[idx]),
+                    };
+                } else {
+                    data.rows[i].cells[j] = Cell::FixedValue {
+                        value: sudoku.digits[idx] as u8,
+                    };
+                }
+            }
+        }
+        data
+    }
+}
+
+impl FromStr for SudokuData {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut data = Self::default();
+        let mut chars = s.chars();
+        for row in &mut data.rows {
+            for cell in &mut row.cells {
+                let c = chars.next().ok_or(crate::Error::GenerateSudoku)?;
+                match c {
+                    '.' => *cell = Cell::default(),
+                    '1'..='9' => {
+                        *cell = Cell::FixedValue {
+                            value: c.to_digit(10).ok_or(crate::Error::GenerateSudoku)? as u8,
+                        }
+                    }
+                    _ => return Err(crate::Error::GenerateSudoku),
+                }
+            }
+        }
+        Ok(data)
+    }
+}
+
+impl Default for SudokuData {
+    fn default() -> Self {
+        Self::new(Variant::default())
+    }
+}
+
+impl SudokuData {
+    #[must_use]
+    pub fn new(variant: Variant) -> Self {
+        let size = variant.size;
+        let row = SudokuRow {
+            cells: vec![Cell::empty(size); size.digits()],
+            marks: vec![CellMarks::default(); size.digits()],
+        };
+        Self {
+            rows: vec![row; size.digits()],
+            variant,
+        }
+    }
+
+    #[must_use]
+    pub const fn size(&self) -> Size {
+        self.variant.size
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: u8, fixed: bool) {
+        match self.rows[row].cells[col] {
+            Cell::Empty { choices } => {
+                if fixed {
+                    self.rows[row].cells[col] = Cell::FixedValue { value };
+                } else {
+                    self.rows[row].cells[col] = Cell::Value { value, choices };
+                }
+
+                for (r, c) in self.get_peer_positions(row, col) {
+                    self.remove_choice(r, c, value);
+                }
+                self.apply_constraints();
+            }
+            Cell::Value { .. }
+            | Cell::FixedValue { .. }
+            | Cell::Error { .. }
+            | Cell::AnimatedValue { .. } => {}
+        }
+    }
+
+    pub fn set_fade(&mut self, row: usize, col: usize, value: u8, fade_delay_ms: i32) {
+        match self.rows[row].cells[col] {
+            Cell::Empty { .. } => {
+                self.rows[row].cells[col] = Cell::AnimatedValue {
+                    value,
+                    choices: [false; MAX_DIGITS],
+                    fade_delay_ms,
+                    animation: "fade-in".to_string(),
+                };
+            }
+            Cell::Value { .. }
+            | Cell::FixedValue { .. }
+            | Cell::Error { .. }
+            | Cell::AnimatedValue { .. } => {}
+        }
+    }
+
+    pub fn unset(&mut self, row: usize, col: usize) {
+        match self.rows[row].cells[col] {
+            Cell::Empty { .. } | Cell::FixedValue { .. } => {}
+            Cell::Value { value, .. }
+            | Cell::Error { value, .. }
+            | Cell::AnimatedValue { value, .. } => {
+                self.rows[row].cells[col] = Cell::Empty {
+                    choices: self.available_choices(row, col),
+                };
+                let constraints = self.variant.constraints();
+                let peers = self.get_peer_positions(row, col);
+                let n = self.size().digits();
+                let others = (0..n)
+                    .flat_map(|r| (0..n).map(move |c| (r, c)))
+                    .filter(|&pos| pos != (row, col));
+                for (r, c) in others {
+                    if constraints
+                        .iter()
+                        .any(|constraint| constraint.links((row, col), (r, c)))
+                    {
+                        // e.g. a cage sum may now allow other digits as well
+                        self.restore_choices(r, c);
+                    } else if peers.contains(&(r, c))
+                        && self.available_choices(r, c)[(value - 1) as usize]
+                    {
+                        self.add_choice(r, c, value);
+                    }
+                }
+                self.apply_constraints();
+            }
+        }
+    }
+
+    pub fn apply_constraints(&mut self) {
+        let n = self.size().digits();
+        let available: Vec<_> = (0..n)
+            .flat_map(|row| (0..n).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                self.rows[row].cells[col]
+                    .is_empty()
+                    .then(|| self.available_choices(row, col))
+            })
+            .collect();
+        let digits = self.digits();
+        let mut candidates: Vec<u32> = self
+            .rows
+            .iter()
+            .flat_map(|row| &row.cells)
+            .map(|cell| match cell {
+                Cell::Empty { choices } => u32::from(to_int(choices)) << 1,
+                Cell::Value { .. }
+                | Cell::FixedValue { .. }
+                | Cell::Error { .. }
+                | Cell::AnimatedValue { .. } => 0,
+            })
+            .collect();
+        for constraint in self.variant.constraints() {
+            constraint.prune(self.size(), &digits, &mut candidates);
+        }
+        let cells = self.rows.iter_mut().flat_map(|row| &mut row.cells);
+        for ((cell, mask), available) in cells.zip(candidates).zip(available) {
+            if let (Cell::Empty { choices }, Some(available)) = (cell, available) {
+                for (i, choice) in choices.iter_mut().enumerate() {
+                    *choice &= available[i] && mask & (1 << (i + 1)) != 0;
+                }
+            }
+        }
+    }
+
+    pub fn toggle_choice(&mut self, row: usize, col: usize, digit: u8) {
+        if let Cell::Empty { choices } = self.rows[row].cells[col] {
+            self.set_choice(row, col, digit, !choices[(digit - 1) as usize]);
+        }
+    }
+
+    pub fn set_choice(&mut self, row: usize, col: usize, digit: u8, enabled: bool) {
+        let idx = (digit - 1) as usize;
+        let sudoku_row = &mut self.rows[row];
+        if let Cell::Empty { choices } = &mut sudoku_row.cells[col] {
+            choices[idx] = enabled;
+            sudoku_row.marks[col].eliminated[idx] = !enabled;
+        }
+    }
+
+    pub fn clear_eliminations(&mut self, row: usize, col: usize) {
+        self.rows[row].marks[col].eliminated = [false; MAX_DIGITS];
+        self.restore_choices(row, col);
+        self.apply_constraints();
+    }
+
+    #[must_use]
+    pub const fn marks(&self, row: usize, col: usize) -> &CellMarks {
+        &self.rows[row].marks[col]
+    }
+
+    pub fn set_pencil_mark(
+        &mut self,
+        row: usize,
+        col: usize,
+        layer: PencilMark,
+        digit: u8,
+        enabled: bool,
+    ) {
+        let marks = &mut self.rows[row].marks[col];
+        let layer = match layer {
+            PencilMark::Corner => &mut marks.corner,
+            PencilMark::Centre => &mut marks.centre,
+        };
+        layer[(digit - 1) as usize] = enabled;
+    }
+
+    pub fn set_color(&mut self, row: usize, col: usize, color: Option<Color>) {
+        self.rows[row].marks[col].color = color;
+    }
+
+    pub fn set_choice_color(&mut self, row: usize, col: usize, digit: u8, color: Option<Color>) {
+        self.rows[row].marks[col].choice_colors[(digit - 1) as usize] = color;
+    }
+
+    pub fn without_eliminated(
+        &self,
+        row: usize,
+        col: usize,
+        choices: [bool; MAX_DIGITS],
+    ) -> [bool; MAX_DIGITS] {
+        let eliminated = &self.rows[row].marks[col].eliminated;
+        let mut result = choices;
+        for (choice, &removed) in result.iter_mut().zip(eliminated) {
+            *choice &= !removed;
+        }
+        result
+    }
+
+    fn restore_choices(&mut self, row: usize, col: usize) {
+        let available = self.available_choices(row, col);
+        if let Cell::Empty { choices } = &mut self.rows[row].cells[col] {
+            for (choice, &restored) in choices.iter_mut().zip(&available) {
+                *choice |= restored;
+            }
+        }
+    }
+
+    fn available_choices(&self, row: usize, col: usize) -> [bool; MAX_DIGITS] {
+        let mut choices = self.size().all_digits();
+        let peers = self.variant.peer_table();
+        for &(r, c) in &peers[row * self.size().digits() + col] {
+            if let Some(value) = self.rows[r].cells[c].value() {
+                choices[(value - 1) as usize] = false;
+            }
+        }
+        self.without_eliminated(row, col, choices)
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> Cell {
+        self.rows[row].cells[col].clone()
+    }
+
+    #[must_use]
+    pub fn fixed_digits(&self) -> Vec<u8> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.cells)
+            .map(|cell| match cell {
+                Cell::FixedValue { value } => *value,
+                Cell::Empty { .. }
+                | Cell::Value { .. }
+                | Cell::Error { .. }
+                | Cell::AnimatedValue { .. } => 0,
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn digits(&self) -> Vec<u8> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.cells)
+            .map(|cell| cell.value().unwrap_or(0))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn are_peers(&self, cell1: (usize, usize), cell2: (usize, usize)) -> bool {
+        self.variant.are_peers(cell1, cell2)
+    }
+
+    fn get_peer_positions(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        self.variant.peers(row, col)
+    }
+
+    #[allow(dead_code)]
+    fn get_only_choice(choices: &[bool; MAX_DIGITS]) -> Option<u8> {
+        let mut count = 0;
+        let mut value = 0;
+        for (i, &choice) in choices.iter().enumerate() {
+            if choice {
+                count += 1;
+                value = i + 1;
+            }
+        }
+        if count == 1 {
+            Some(value as u8)
+        } else {
+            None
+        }
+    }
+
+    fn remove_choice(&mut self, row: usize, col: usize, value: u8) {
+        if let Cell::Empty { choices } = &mut self.rows[row].cells[col] {
+            choices[(value - 1) as usize] = false;
+        }
+    }
+
+    fn add_choice(&mut self, row: usize, col: usize, value: u8) {
+        if let Cell::Empty { choices } = &mut self.rows[row].cells[col] {
+            choices[(value - 1) as usize] = true;
+        }
+    }
+
+    #[must_use]
+    pub fn to_compressed(&self) -> String {
+        compress_string(&self.to_string()).unwrap_or_default()
+    }
+}
+
+impl Display for SudokuData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.size().format(&self.digits()))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use std::error::Error;
+
+    use crate::techniques::check_constraints;
+
+    use super::*;
+
+    type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+    #[test]
+    fn test_serialize_sudoku_data() -> Result<()> {
+        let data = SudokuData::default();
+        let serialized = serde_json::to_string(&data)?;
+        // dbg!(&serialized);
+        // dbg!(&serialized.len());
+        assert!(serialized.len() < 4000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_checks_board() -> Result<()> {
+        let six = SudokuData::new("s6".parse()?);
+        let serialized = serde_json::to_string(&six)?;
+        let deserialized: SudokuData = serde_json::from_str(&serialized)?;
+        assert_eq!(deserialized.rows.len(), 6);
+
+        // nine rows do not fit a 6×6 variant
+        let mismatched = SudokuData {
+            variant: "s6".parse()?,
+            ..Default::default()
+        };
+        let serialized = serde_json::to_string(&mismatched)?;
+        assert!(serde_json::from_str::<SudokuData>(&serialized).is_err());
+
+        let mut too_big = SudokuData::default();
+        too_big.rows[0].cells[0] = Cell::FixedValue { value: 10 };
+        let serialized = serde_json::to_string(&too_big)?;
+        assert!(serde_json::from_str::<SudokuData>(&serialized).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_checks_variant() -> Result<()> {
+        let thermo = Variant::from_str("t112131")?;
+        let serialized = serde_json::to_string(&thermo)?;
+        assert_eq!(serde_json::from_str::<Variant>(&serialized)?, thermo);
+
+        // a cage of a 9×9 board does not fit a 4×4 one
+        let mut four = Variant::from_str("s4")?;
+        four.cages = Variant::from_str("k10-9899")?.cages;
+        let serialized = serde_json::to_string(&four)?;
+        assert!(serde_json::from_str::<Variant>(&serialized).is_err());
+
+        // and a thermometer may not skip a cell
+        let mut gap = thermo;
+        gap.thermos[0].cells.remove(1);
+        let serialized = serde_json::to_string(&gap)?;
+        assert!(serde_json::from_str::<Variant>(&serialized).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_techniques_keep_diagonal_peers() -> Result<()> {
+        let mut data = SudokuData::new(Variant::from_str("x")?);
+        data.set(0, 0, 5, false);
+        check_constraints(&mut data)?;
+        for i in 1..9 {
+            let Cell::Empty { choices } = data.get(i, i) else {
+                return Err("diagonal cell is not empty".into());
+            };
+            assert!(!choices[4]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unset_keeps_manual_eliminations() {
+        let mut data = SudokuData::default();
+        data.toggle_choice(0, 1, 5);
+        data.set(0, 0, 3, false);
+        data.set(4, 4, 3, false);
+        data.unset(0, 0);
+
+        let mut without_5 = Size::Nine.all_digits();
+        without_5[4] = false;
+        assert_eq!(data.get(0, 0), Cell::default());
+        assert_eq!(data.get(0, 1), Cell::Empty { choices: without_5 });
+
+        let mut without_3 = Size::Nine.all_digits();
+        without_3[2] = false;
+        assert_eq!(data.get(4, 0), Cell::Empty { choices: without_3 });
+        assert_eq!(data.get(8, 8), Cell::default());
+    }
+
+    #[test]
+    fn test_serialize_manual_eliminations() -> Result<()> {
+        let mut data = SudokuData::default();
+        data.toggle_choice(2, 7, 9);
+        let serialized = serde_json::to_string(&data)?;
+        let deserialized: SudokuData = serde_json::from_str(&serialized)?;
+        assert!(deserialized.rows[2].marks[7].eliminated[8]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_killer_candidates() -> Result<()> {
+        let mut data = SudokuData {
+            variant: "k3-1112_k17-2131".parse()?,
+            ..Default::default()
+        };
+        data.apply_constraints();
+        assert_eq!(
+            data.get(0, 0),
+            Cell::Empty {
+                choices: from_int(0b11)
+            }
+        );
+        assert_eq!(
+            data.get(1, 0),
+            Cell::Empty {
+                choices: from_int(0b1_1000_0000)
+            }
+        );
+
+        data.set(0, 0, 1, false);
+        assert_eq!(
+            data.get(0, 1),
+            Cell::Empty {
+                choices: from_int(0b10)
+            }
+        );
+        data.unset(0, 0);
+        assert_eq!(
+            data.get(0, 1),
+            Cell::Empty {
+                choices: from_int(0b11)
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_chess_candidates() -> Result<()> {
+        let mut data = SudokuData::new("knight".parse()?);
+        data.set(4, 4, 1, false);
+        let without_one = Cell::Empty {
+            choices: from_int(0b1_1111_1110),
+        };
+        assert_eq!(data.get(2, 3), without_one);
+        assert_eq!(data.get(6, 5), without_one);
+        assert_eq!(data.get(2, 2), Cell::default());
+
+        data.unset(4, 4);
+        assert_eq!(data.get(2, 3), Cell::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_six_by_six_board() -> Result<()> {
+        let mut data = SudokuData::new("s6".parse()?);
+        assert_eq!(data.rows.len(), 6);
+        assert_eq!(
+            data.get(5, 5),
+            Cell::Empty {
+                choices: from_int(0b11_1111)
+            }
+        );
+
+        data.set(0, 0, 6, true);
+        assert_eq!(
+            data.get(1, 2),
+            Cell::Empty {
+                choices: from_int(0b1_1111)
+            }
+        );
+        assert_eq!(
+            data.get(2, 1),
+            Cell::Empty {
+                choices: from_int(0b11_1111)
+            }
+        );
+        assert_eq!(data.to_string(), format!("6{}", ".".repeat(35)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_serialize_colors() -> Result<()> {
+        let mut data = SudokuData::default();
+        data.set_color(3, 3, Some(Color::Green));
+        data.set_choice_color(3, 4, 2, Some(Color::Purple));
+        let serialized = serde_json::to_string(&data)?;
+        let deserialized: SudokuData = serde_json::from_str(&serialized)?;
+        assert_eq!(deserialized.marks(3, 3).color, Some(Color::Green));
+        assert_eq!(
+            deserialized.marks(3, 4).choice_colors[1],
+            Some(Color::Purple)
+        );
+        assert_eq!(deserialized.marks(3, 5), &CellMarks::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_serialize_pencil_marks() -> Result<()> {
+        let mut data = SudokuData::default();
+        data.set_pencil_mark(0, 0, PencilMark::Corner, 1, true);
+        data.set_pencil_mark(0, 0, PencilMark::Centre, 9, true);
+        let serialized = serde_json::to_string(&data)?;
+        let deserialized: SudokuData = serde_json::from_str(&serialized)?;
+        let marks = deserialized.marks(0, 0);
+        assert!(marks.has_pencil_marks());
+        assert!(marks.pencil_marks(PencilMark::Corner)[0]);
+        assert!(marks.pencil_marks(PencilMark::Centre)[8]);
+        assert!(!marks.pencil_marks(PencilMark::Centre)[0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+fn line_orders() -> Vec<[usize; 9]> {
+    (0..PERMUTATIONS.len().pow(4))
+        .map(|i| {
+            let bands = PERMUTATIONS[i % 6];
+            let mut order = [0; 9];
+            for (k, line) in order.iter_mut().enumerate() {
+                let within = PERMUTATIONS[i / 6_usize.pow(1 + k as u32 / 3) % 6];
+                *line = bands[k / 3] * 3 + within[k % 3];
+            }
+            order
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn canonicalize(givens: &[u8]) -> Vec<u8> {
+    let orders = line_orders();
+    let transposed: Vec<u8> = (0..81).map(|idx| givens[idx % 9 * 9 + idx / 9]).collect();
+    let mut best = [u8::MAX; 81];
+    for board in [givens, &transposed] {
+        for rows in &orders {
+            for cols in &orders {
+                keep_if_smaller(board, rows, cols, &mut best);
+            }
+        }
+    }
+    best.to_vec()
+}
+
+fn keep_if_smaller(board: &[u8], rows: &[usize; 9], cols: &[usize; 9], best: &mut [u8; 81]) {
+    let mut labels = [0; 10];
+    let mut next = 1;
+    let mut candidate = [0; 81];
+    let mut smaller = false;
+    let cells = rows
+        .iter()
+        .flat_map(|&row| cols.iter().map(move |&col| board[row * 9 + col]));
+    for (idx, digit) in cells.enumerate() {
+        let digit = usize::from(digit);
+        if digit != 0 && labels[digit] == 0 {
+            labels[digit] = next;
+            next += 1;
+        }
+        candidate[idx] = labels[digit];
+        smaller = smaller || candidate[idx] < best[idx];
+        if !smaller && candidate[idx] > best[idx] {
+            return;
+        }
+    }
+    if smaller {
+        *best = candidate;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use crate::{generator::transmute, size::Size, test_support::EASY_SUDOKU, Error, Result};
+
+    use super::*;
+
+    #[test]
+    fn test_line_orders() {
+        let orders = line_orders();
+        assert_eq!(orders.len(), 1_296);
+        assert_eq!(orders[0], [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(orders.iter().all(|order| {
+            let mut sorted = *order;
+            sorted.sort_unstable();
+            sorted == [0, 1, 2, 3, 4, 5, 6, 7, 8]
+        }));
+    }
+
+    #[test]
+    fn test_canonicalize_transmuted() -> Result<()> {
+        let givens = Size::Nine.parse(EASY_SUDOKU).ok_or(Error::ParsePuzzle)?;
+        let canonical = canonicalize(&givens);
+        assert_eq!(canonicalize(&canonical), canonical);
+        for _ in 0..3 {
+            let mut arr = EASY_SUDOKU.as_bytes().to_vec();
+            transmute(&mut arr);
+            let transmuted = Size::Nine
+                .parse(&String::from_utf8(arr)?)
+                .ok_or(Error::ParsePuzzle)?;
+            assert_eq!(canonicalize(&transmuted), canonical);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+use derive_more::From;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use rust_sudoku_solver::{solver, Sudoku};
+use web_time::Instant;
+
+use crate::backtrack;
+use crate::encoding::{sudoku_digits, to_sudoku};
+use crate::size::MAX_DIGITS;
+use crate::sudoku_data::{Cell, SudokuData};
+use crate::variant::Variant;
+use crate::Result;
+
+#[derive(Debug, Clone, Copy, From)]
+pub struct Duration(pub web_time::Duration);
+
+#[allow(clippy::use_debug)]
+impl std::fmt::Display for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+trait TimedAction<T, E> {
+    #[allow(unused)]
+    fn map_timed<F, U>(self, f: F) -> std::result::Result<(U, Duration), E>
+    where
+        F: FnOnce(T) -> U;
+    fn and_then_timed<F, U>(self, f: F) -> std::result::Result<(U, Duration), E>
+    where
+        F: FnOnce(T) -> std::result::Result<U, E>;
+}
+
+impl<T, E> TimedAction<T, E> for std::result::Result<T, E> {
+    fn map_timed<F, U>(self, f: F) -> std::result::Result<(U, Duration), E>
+    where
+        F: FnOnce(T) -> U,
+    {
+        self.map(|t| {
+            let now = Instant::now();
+            (f(t), now.elapsed().into())
+        })
+    }
+
+    fn and_then_timed<F, U>(self, f: F) -> std::result::Result<(U, Duration), E>
+    where
+        F: FnOnce(T) -> std::result::Result<U, E>,
+    {
+        self.and_then(|t| {
+            let now = Instant::now();
+            f(t).map(|u| (u, now.elapsed().into()))
+        })
+    }
+}
+
+/// # Errors
+///
+/// Fails if the givens have no solution.
+pub fn verify_sudoku(sudoku_data: &mut SudokuData) -> Result<String> {
+    Ok(sudoku_data)
+        .and_then_timed(compare_with_solution)
+        .map(|((), elapsed)| format!("Sudoku verified in {elapsed}"))
+}
+
+/// # Errors
+///
+/// Fails if the digits on the board have no solution, after marking the
+/// placed digits that are wrong.
+pub fn solve_sudoku(sudoku_data: &mut SudokuData) -> Result<String> {
+    Ok(sudoku_data.digits())
+        .and_then_timed(|digits| find_solution(&digits, &sudoku_data.variant))
+        .map(|(solution, elapsed)| {
+            update_from_digits_animated(sudoku_data, &solution);
+            elapsed
+        })
+        .map_err(|err| {
+            let r = compare_with_solution(sudoku_data);
+            match r {
+                Ok(()) => err,
+                Err(e) => e,
+            }
+        })
+        .map(|elapsed| format!("Sudoku solved in {elapsed}"))
+}
+
+/// # Errors
+///
+/// Fails if the board has no solution.
+pub fn find_solution(givens: &[u8], variant: &Variant) -> Result<Vec<u8>> {
+    if variant.is_classic() {
+        Ok(sudoku_digits(&solver::solve(to_sudoku(givens))?))
+    } else {
+        backtrack::solve(givens, variant)
+    }
+}
+
+/// # Errors
+///
+/// Fails on boards that the classic techniques do not apply to, see
+/// [`Variant::check_classic_techniques`].
+pub fn place_all_visible_singles(sudoku: &mut SudokuData) -> Result<String> {
+    apply_constraint(sudoku, rust_sudoku_solver::place_all_visible_singles)
+        .map(|elapsed| format!("Visible singles placed in {elapsed}"))
+}
+
+/// # Errors
+///
+/// Fails like [`place_all_visible_singles`].
+pub fn place_all_hidden_singles(sudoku: &mut SudokuData) -> Result<String> {
+    apply_constraint(sudoku, rust_sudoku_solver::place_all_hidden_singles)
+        .map(|elapsed| format!("Hidden singles placed in {elapsed}"))
+}
+
+/// # Errors
+///
+/// Fails like [`place_all_visible_singles`].
+pub fn check_all_visible_doubles(sudoku: &mut SudokuData) -> Result<String> {
+    apply_constraint(sudoku, rust_sudoku_solver::check_all_visible_doubles)
+        .map(|elapsed| format!("Doubles checked in {elapsed}"))
+}
+
+/// # Errors
+///
+/// Fails like [`place_all_visible_singles`].
+pub fn check_triples(sudoku: &mut SudokuData) -> Result<String> {
+    apply_constraint(sudoku, rust_sudoku_solver::check_triples)
+        .map(|elapsed| format!("Triples checked in {elapsed}"))
+}
+
+/// # Errors
+///
+/// Fails like [`place_all_visible_singles`].
+pub fn check_constraints(sudoku: &mut SudokuData) -> Result<String> {
+    apply_constraint(sudoku, rust_sudoku_solver::check_constraints)
+        .map(|elapsed| format!("Constraints checked in {elapsed}"))
+}
+
+/// # Errors
+///
+/// Never fails, the `Result` is there to match the other techniques.
+pub fn check_x_wings(sudoku: &mut SudokuData) -> Result<String> {
+    let now = Instant::now();
+    let n = sudoku.size().digits();
+    for digit in 0..n {
+        for by_rows in [true, false] {
+            let pos = |line: usize, i: usize| if by_rows { (line, i) } else { (i, line) };
+            let spots: Vec<Vec<usize>> = (0..n)
+                .map(|line| {
+                    (0..n)
+                        .filter(|&i| {
+                            let (row, col) = pos(line, i);
+                            matches!(sudoku.get(row, col), Cell::Empty { choices } if choices[digit])
+                        })
+                        .collect()
+                })
+                .collect();
+            let pairs = spots
+                .iter()
+                .enumerate()
+                .filter(|(_, cells)| cells.len() == 2);
+            for (a, first) in pairs {
+                let same = spots.iter().enumerate().skip(a + 1);
+                for (b, _) in same.filter(|&(_, second)| second == first) {
+                    for line in (0..n).filter(|&line| line != a && line != b) {
+                        for &i in first {
+                            let (row, col) = pos(line, i);
+                            if let Cell::Empty { choices } = &mut sudoku.rows[row].cells[col] {
+                                choices[digit] = false;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(format!("X-Wings checked in {}", Duration(now.elapsed())))
+}
+
+#[must_use]
+pub fn to_choices(bitboard: usize) -> [bool; MAX_DIGITS] {
+    let mut choices = [false; MAX_DIGITS];
+    for i in 1..=9 {
+        choices[i - 1] = (bitboard & (1 << i)) != 0;
+    }
+    choices
+}
+
+#[must_use]
+pub fn from_choices(choices: &[bool; MAX_DIGITS]) -> usize {
+    (1..=9)
+        .filter(|&i| choices[i - 1])
+        .fold(0, |bitboard, i| bitboard | (1 << i))
+}
+
+pub fn update_from_digits(sudoku: &mut SudokuData, digits: &[u8], fixed: bool) {
+    let n = sudoku.size().digits();
+    for (idx, &digit) in digits.iter().enumerate() {
+        if digit != 0 {
+            sudoku.set(idx / n, idx % n, digit, fixed);
+        }
+    }
+    sudoku.apply_constraints();
+}
+
+pub fn update_from_digits_animated(sudoku: &mut SudokuData, digits: &[u8]) {
+    let n = sudoku.size().digits();
+    let mut vec: Vec<usize> = (0..digits.len()).collect();
+    let mut duration = 0;
+    vec.shuffle(&mut thread_rng());
+    for &idx in &vec {
+        if digits[idx] != 0 {
+            sudoku.set_fade(idx / n, idx % n, digits[idx], duration);
+        }
+        duration += 5;
+    }
+    sudoku.apply_constraints();
+}
+
+pub fn update_from_sudoku_animated(sudoku: &mut SudokuData, solution: &Sudoku, _fixed: bool) {
+    let mut vec: Vec<usize> = (0..81).collect();
+    let mut duration = 0;
+    vec.shuffle(&mut thread_rng());
+    for &idx in &vec {
+        let i = idx / 9;
+        let j = idx % 9;
+        let solution = solution.clone();
+        if solution.digits[idx] == 0 {
+            sudoku.rows[i].cells[j] = Cell::Empty {
+                choices: sudoku.without_eliminated(i, j, to_choices(solution.bitboard[idx])),
+            };
+        } else {
+            sudoku.set_fade(i, j, (solution.digits[idx]) as u8, duration);
+        }
+        duration += 5;
+    }
+    sudoku.apply_constraints();
+}
+
+/// # Errors
+///
+/// Fails if the givens have no solution.
+pub fn compare_with_solution(sudoku: &mut SudokuData) -> Result<()> {
+    let solution = find_solution(&sudoku.fixed_digits(), &sudoku.variant)?;
+    mark_against_solution(sudoku, &solution);
+    Ok(())
+}
+
+pub fn mark_against_solution(sudoku: &mut SudokuData, solution: &[u8]) {
+    let n = sudoku.size().digits();
+
+    for i in 0..n {
+        for j in 0..n {
+            mark_cell(sudoku, i, j, solution[n * i + j]);
+        }
+    }
+}
+
+/// Tells whether the digit in the cell is a new mistake, i.e. wrong and not
+/// marked as such yet.
+pub fn mark_cell(sudoku: &mut SudokuData, row: usize, col: usize, solution: u8) -> bool {
+    let cell = sudoku.rows[row].cells[col].clone();
+    match cell {
+        Cell::Value { value, choices } | Cell::AnimatedValue { value, choices, .. } => {
+            if value == solution {
+                sudoku.rows[row].cells[col] = Cell::AnimatedValue {
+                    value,
+                    choices,
+                    fade_delay_ms: 100,
+                    animation: "fade-green".to_string(),
+                };
+                false
+            } else {
+                sudoku.rows[row].cells[col] = Cell::Error { value, choices };
+                true
+            }
+        }
+        Cell::Error { .. } | Cell::Empty { .. } | Cell::FixedValue { .. } => false,
+    }
+}
+
+fn apply_constraint(
+    sudoku_data: &mut SudokuData,
+    f: impl Fn(&mut Sudoku) -> rust_sudoku_solver::Result<()>,
+) -> Result<Duration> {
+    sudoku_data.variant.check_classic_techniques()?;
+    Ok(Sudoku::from(&*sudoku_data))
+        .and_then_timed(|mut sudoku| {
+            f(&mut sudoku)?;
+            Ok(sudoku)
+        })
+        .map(|(solution, elapsed)| {
+            update_from_sudoku_animated(sudoku_data, &solution, false);
+            elapsed
+        })
+}
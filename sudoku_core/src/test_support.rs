@@ -0,0 +1,10 @@
+pub(crate) const EASY_SUDOKU: &str =
+    "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+
+// the singles get stuck until a naked pair is found
+pub(crate) const PAIRS_SUDOKU: &str =
+    ".92.1....5317..2..........332.15.7.9...6.3..5....2.........9...71..34...26....43.";
+
+// stuck after the triples until an X-Wing is found
+pub(crate) const FISH_SUDOKU: &str =
+    "..7......1...3.57.63..12.....35.4.17.....98.....3..2....6....91..2.4....8....6...";
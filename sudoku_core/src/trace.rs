@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{sudoku_data::SudokuData, techniques::update_from_digits, variant::Variant};
+
+/// Keeps traces small enough to be sent to the web app.
+pub const MAX_STEPS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Step {
+    Place { cell: usize, digit: u8 },
+    Eliminate { cell: usize, digit: u8 },
+    Backtrack { cell: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trace {
+    pub givens: Vec<u8>,
+    pub variant: Variant,
+    pub steps: Vec<Step>,
+    pub truncated: bool,
+    pub solution: Option<Vec<u8>>,
+}
+
+impl Step {
+    pub fn apply(self, sudoku: &mut SudokuData) {
+        let n = sudoku.size().digits();
+        match self {
+            Self::Place { cell, digit } => sudoku.set(cell / n, cell % n, digit, false),
+            Self::Eliminate { cell, digit } => {
+                sudoku.unset(cell / n, cell % n);
+                sudoku.set_choice(cell / n, cell % n, digit, false);
+            }
+            Self::Backtrack { cell } => sudoku.clear_eliminations(cell / n, cell % n),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Replay {
+    trace: Trace,
+    position: usize,
+}
+
+impl Replay {
+    #[must_use]
+    pub const fn new(trace: Trace) -> Self {
+        Self { trace, position: 0 }
+    }
+
+    #[must_use]
+    pub fn board(&self) -> SudokuData {
+        let mut sudoku = SudokuData::new(self.trace.variant.clone());
+        update_from_digits(&mut sudoku, &self.trace.givens, true);
+        sudoku
+    }
+
+    #[must_use]
+    pub fn matches(&self, sudoku: &SudokuData) -> bool {
+        sudoku.variant == self.trace.variant && sudoku.fixed_digits() == self.trace.givens
+    }
+
+    pub fn step(&mut self, sudoku: &mut SudokuData) -> bool {
+        if self.is_finished() || !self.matches(sudoku) {
+            return false;
+        }
+        if let Some(step) = self.trace.steps.get(self.position) {
+            step.apply(sudoku);
+        } else {
+            // a truncated search may have stopped anywhere
+            *sudoku = self.board();
+            if let Some(solution) = &self.trace.solution {
+                update_from_digits(sudoku, solution, false);
+            }
+        }
+        self.position += 1;
+        !self.is_finished()
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.position > self.trace.steps.len()
+    }
+
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    #[must_use]
+    pub fn step_count(&self) -> usize {
+        self.trace.steps.len()
+    }
+
+    #[must_use]
+    pub const fn trace(&self) -> &Trace {
+        &self.trace
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{backtrack::trace, puzzle::Puzzle, test_support::EASY_SUDOKU, Result};
+
+    use super::*;
+
+    fn play(replay: &mut Replay) -> SudokuData {
+        let mut sudoku = replay.board();
+        while replay.step(&mut sudoku) {}
+        sudoku
+    }
+
+    #[test]
+    fn test_replay_ends_with_solution() -> Result<()> {
+        let puzzle = Puzzle::from_str(EASY_SUDOKU)?;
+        let trace = trace(&puzzle.givens, &puzzle.variant, MAX_STEPS);
+        assert!(!trace.truncated);
+        assert!(!trace.steps.is_empty());
+        let solution = trace.solution.clone();
+        let mut replay = Replay::new(trace);
+        let sudoku = play(&mut replay);
+        assert!(replay.is_finished());
+        assert_eq!(Some(sudoku.digits()), solution);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay_stops_on_other_puzzle() -> Result<()> {
+        let puzzle = Puzzle::from_str(EASY_SUDOKU)?;
+        let mut replay = Replay::new(trace(&puzzle.givens, &puzzle.variant, MAX_STEPS));
+        let mut other = SudokuData::new(Variant::from_str("s4")?);
+        assert!(!replay.matches(&other));
+        assert!(!replay.step(&mut other));
+        assert_eq!(replay.position(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncated_trace() -> Result<()> {
+        let puzzle = Puzzle::from_str(EASY_SUDOKU)?;
+        let trace = trace(&puzzle.givens, &puzzle.variant, 5);
+        assert!(trace.truncated);
+        assert_eq!(trace.steps.len(), 5);
+        let solution = trace.solution.clone();
+        assert!(solution.is_some());
+        assert_eq!(Some(play(&mut Replay::new(trace)).digits()), solution);
+        Ok(())
+    }
+}
@@ -0,0 +1,661 @@
+use serde::{Deserialize, Serialize};
+use serde_compact::compact;
+use std::{cell::RefCell, fmt::Display, rc::Rc, str::FromStr};
+
+use crate::{
+    constraint::{AntiKing, AntiKnight, Columns, Constraint, Diagonals, Pos, Regions, Rows},
+    killer::Cage,
+    lines::{Arrow, Dot, DotKind, Thermometer},
+    size::Size,
+    Error, Result,
+};
+
+/// Variants are written as tokens separated by `_`, where cells are given as
+/// a row followed by a column, both starting at 1 and continuing with `a` to
+/// `g` for 10 to 16:
+///
+/// - `s<side>`: a board with 4, 6 or 16 rows instead of 9, e.g. `s6`
+/// - `x`: the two main diagonals are houses as well (Sudoku-X)
+/// - `knight`: equal digits may not be a chess knight's move apart
+/// - `king`: equal digits may not be a chess king's move apart
+/// - `j<regions>`: jigsaw regions replacing the boxes, given as the region of
+///   every cell in row-major order, with regions numbered like rows
+/// - `k<sum>-<cells>`: a killer cage, e.g. `k10-1112`
+/// - `t<cells>`: a thermometer from the bulb on, e.g. `t112131`
+/// - `a<cells>`: an arrow from the circle on, e.g. `a112122`
+/// - `w<cells>` and `b<cells>`: a white or black Kropki dot between two
+///   neighbouring cells, e.g. `w1112`
+#[compact]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedVariant")]
+pub struct Variant {
+    #[serde(default, skip_serializing_if = "is_default_size")]
+    pub size: Size,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub diagonal: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub anti_knight: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub anti_king: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cages: Vec<Cage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thermos: Vec<Thermometer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arrows: Vec<Arrow>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dots: Vec<Dot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<u8>,
+}
+
+thread_local! {
+    // the board asks for the peers of every cell on every move and render,
+    // while the variant rarely changes
+    static PEERS: RefCell<Option<(Variant, Rc<[Vec<Pos>]>)>> = RefCell::default();
+}
+
+#[compact]
+#[derive(Deserialize)]
+struct UncheckedVariant {
+    #[serde(default)]
+    size: Size,
+    #[serde(default)]
+    diagonal: bool,
+    #[serde(default)]
+    anti_knight: bool,
+    #[serde(default)]
+    anti_king: bool,
+    #[serde(default)]
+    cages: Vec<Cage>,
+    #[serde(default)]
+    thermos: Vec<Thermometer>,
+    #[serde(default)]
+    arrows: Vec<Arrow>,
+    #[serde(default)]
+    dots: Vec<Dot>,
+    #[serde(default)]
+    regions: Vec<u8>,
+}
+
+impl TryFrom<UncheckedVariant> for Variant {
+    type Error = Error;
+
+    fn try_from(unchecked: UncheckedVariant) -> Result<Self> {
+        let variant = Self {
+            size: unchecked.size,
+            diagonal: unchecked.diagonal,
+            anti_knight: unchecked.anti_knight,
+            anti_king: unchecked.anti_king,
+            cages: unchecked.cages,
+            thermos: unchecked.thermos,
+            arrows: unchecked.arrows,
+            dots: unchecked.dots,
+            regions: unchecked.regions,
+        };
+        // cells off the board can not be written out
+        variant.check_cells()?;
+        if Self::from_str(&variant.to_string())? != variant {
+            return Err(Error::ParseVariant);
+        }
+        Ok(variant)
+    }
+}
+
+impl Variant {
+    #[must_use]
+    pub const fn is_classic(&self) -> bool {
+        matches!(self.size, Size::Nine)
+            && !self.diagonal
+            && !self.anti_knight
+            && !self.anti_king
+            && self.cages.is_empty()
+            && self.thermos.is_empty()
+            && self.arrows.is_empty()
+            && self.dots.is_empty()
+            && self.regions.is_empty()
+    }
+
+    /// # Errors
+    ///
+    /// Fails with [`Error::UnsupportedSize`] or [`Error::UnsupportedRegions`].
+    pub fn check_classic_techniques(&self) -> Result<()> {
+        if self.size != Size::Nine {
+            return Err(Error::UnsupportedSize);
+        }
+        if !self.regions.is_empty() {
+            return Err(Error::UnsupportedRegions);
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn region(&self, row: usize, col: usize) -> usize {
+        let size = self.size;
+        match self.regions.get(row * size.digits() + col) {
+            Some(&region) => usize::from(region),
+            None => {
+                row / size.box_rows() * (size.digits() / size.box_cols()) + col / size.box_cols()
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn regions(&self) -> Vec<Vec<(usize, usize)>> {
+        let n = self.size.digits();
+        let mut regions = vec![Vec::new(); n];
+        for row in 0..n {
+            for col in 0..n {
+                regions[self.region(row, col)].push((row, col));
+            }
+        }
+        regions
+    }
+
+    #[must_use]
+    pub fn region_borders(&self, row: usize, col: usize) -> [bool; 4] {
+        let n = self.size.digits();
+        let region = self.region(row, col);
+        let differs = |r: Option<usize>, c: Option<usize>| match (r, c) {
+            (Some(r), Some(c)) if r < n && c < n => self.region(r, c) != region,
+            _ => false,
+        };
+        [
+            differs(row.checked_sub(1), Some(col)),
+            differs(Some(row), Some(col + 1)),
+            differs(Some(row + 1), Some(col)),
+            differs(Some(row), col.checked_sub(1)),
+        ]
+    }
+
+    fn check_cells(&self) -> Result<()> {
+        let n = self.size.digits();
+        let cells = self
+            .cages
+            .iter()
+            .flat_map(|cage| &cage.cells)
+            .chain(self.thermos.iter().flat_map(|thermo| &thermo.cells))
+            .chain(self.arrows.iter().flat_map(|arrow| &arrow.cells))
+            .chain(self.dots.iter().flat_map(|dot| &dot.cells));
+        if cells.into_iter().any(|&(row, col)| row >= n || col >= n) {
+            return Err(Error::ParseVariant);
+        }
+        if !self.regions.is_empty() && !self.has_valid_regions() {
+            return Err(Error::ParseVariant);
+        }
+        Ok(())
+    }
+
+    fn has_valid_regions(&self) -> bool {
+        let n = self.size.digits();
+        if self.regions.len() != n * n || self.regions.iter().any(|&r| usize::from(r) >= n) {
+            return false;
+        }
+        self.regions().iter().all(|cells| {
+            let mut reached = vec![cells[0]];
+            let mut i = 0;
+            while let Some(&(row, col)) = reached.get(i) {
+                for neighbour in [(row + 1, col), (row, col + 1)]
+                    .into_iter()
+                    .chain(row.checked_sub(1).map(|r| (r, col)))
+                    .chain(col.checked_sub(1).map(|c| (row, c)))
+                {
+                    if cells.contains(&neighbour) && !reached.contains(&neighbour) {
+                        reached.push(neighbour);
+                    }
+                }
+                i += 1;
+            }
+            cells.len() == n && reached.len() == n
+        })
+    }
+
+    #[must_use]
+    pub fn constraints(&self) -> Vec<Box<dyn Constraint>> {
+        let mut constraints: Vec<Box<dyn Constraint>> = vec![
+            Box::new(Rows),
+            Box::new(Columns),
+            Box::new(Regions(self.regions())),
+        ];
+        if self.diagonal {
+            constraints.push(Box::new(Diagonals));
+        }
+        if self.anti_knight {
+            constraints.push(Box::new(AntiKnight));
+        }
+        if self.anti_king {
+            constraints.push(Box::new(AntiKing));
+        }
+        for cage in &self.cages {
+            constraints.push(Box::new(cage.clone()));
+        }
+        for thermo in &self.thermos {
+            constraints.push(Box::new(thermo.clone()));
+        }
+        for arrow in &self.arrows {
+            constraints.push(Box::new(arrow.clone()));
+        }
+        for dot in &self.dots {
+            constraints.push(Box::new(dot.clone()));
+        }
+        constraints
+    }
+
+    #[must_use]
+    pub fn houses(&self) -> Vec<Vec<Pos>> {
+        self.constraints()
+            .iter()
+            .flat_map(|constraint| constraint.houses(self.size))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn peers(&self, row: usize, col: usize) -> Vec<Pos> {
+        self.peer_table()[row * self.size.digits() + col].clone()
+    }
+
+    #[must_use]
+    pub fn are_peers(&self, cell1: Pos, cell2: Pos) -> bool {
+        cell1 == cell2
+            || self.peer_table()[cell1.0 * self.size.digits() + cell1.1]
+                .binary_search(&cell2)
+                .is_ok()
+    }
+
+    #[must_use]
+    pub fn peer_table(&self) -> Rc<[Vec<Pos>]> {
+        PEERS.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            match &*cache {
+                Some((variant, peers)) if variant == self => Rc::clone(peers),
+                _ => {
+                    let peers: Rc<[Vec<Pos>]> = self.find_peers().into();
+                    *cache = Some((self.clone(), Rc::clone(&peers)));
+                    peers
+                }
+            }
+        })
+    }
+
+    fn find_peers(&self) -> Vec<Vec<Pos>> {
+        let n = self.size.digits();
+        let constraints = self.constraints();
+        let houses = self.houses();
+        let cells: Vec<Pos> = (0..n)
+            .flat_map(|row| (0..n).map(move |col| (row, col)))
+            .collect();
+        cells
+            .iter()
+            .map(|&cell| {
+                let mut peers: Vec<_> = houses
+                    .iter()
+                    .filter(|house| house.contains(&cell))
+                    .flatten()
+                    .copied()
+                    .chain(cells.iter().copied().filter(|&pos| {
+                        constraints
+                            .iter()
+                            .any(|constraint| constraint.separates(cell, pos))
+                    }))
+                    .filter(|&pos| pos != cell)
+                    .collect();
+                peers.sort_unstable();
+                peers.dedup();
+                peers
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn conflicts(&self, digits: &[u8]) -> Vec<Pos> {
+        let n = self.size.digits();
+        let digit = |(row, col): Pos| digits[row * n + col];
+        let peers = self.peer_table();
+        let mut conflicts: Vec<_> = (0..n)
+            .flat_map(|row| (0..n).map(move |col| (row, col)))
+            .filter(|&pos| {
+                digit(pos) != 0
+                    && peers[pos.0 * n + pos.1]
+                        .iter()
+                        .any(|&peer| digit(peer) == digit(pos))
+            })
+            .chain(
+                self.constraints()
+                    .iter()
+                    .flat_map(|constraint| constraint.violations(self.size, digits)),
+            )
+            .collect();
+        conflicts.sort_unstable();
+        conflicts.dedup();
+        conflicts
+    }
+
+    #[must_use]
+    pub const fn diagonals_at(&self, row: usize, col: usize) -> (bool, bool) {
+        let last = self.size.digits() - 1;
+        (
+            self.diagonal && row == col,
+            self.diagonal && row + col == last,
+        )
+    }
+
+    #[must_use]
+    pub fn cage_at(&self, row: usize, col: usize) -> Option<&Cage> {
+        self.cages.iter().find(|cage| cage.contains(row, col))
+    }
+
+    #[must_use]
+    pub fn cage_borders(&self, row: usize, col: usize) -> Option<[bool; 4]> {
+        let cage = self.cage_at(row, col)?;
+        let outside = |r: Option<usize>, c: Option<usize>| match (r, c) {
+            (Some(r), Some(c)) if r < self.size.digits() && c < self.size.digits() => {
+                !cage.contains(r, c)
+            }
+            _ => true,
+        };
+        Some([
+            outside(row.checked_sub(1), Some(col)),
+            outside(Some(row), Some(col + 1)),
+            outside(Some(row + 1), Some(col)),
+            outside(Some(row), col.checked_sub(1)),
+        ])
+    }
+}
+
+fn is_default_size(size: &Size) -> bool {
+    *size == Size::default()
+}
+
+fn parse_index(c: char) -> Option<usize> {
+    match c {
+        '1'..='9' => Some(c as usize - '1' as usize),
+        'a'..='g' => Some(c as usize - 'a' as usize + 9),
+        _ => None,
+    }
+}
+
+/// # Errors
+///
+/// Fails with [`Error::ParseVariant`] unless `s` holds cells written as a row
+/// followed by a column, e.g. `1112`.
+pub fn parse_cells(s: &str) -> Result<Vec<(usize, usize)>> {
+    let digits = s
+        .chars()
+        .map(parse_index)
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Error::ParseVariant)?;
+    if digits.len() % 2 != 0 {
+        return Err(Error::ParseVariant);
+    }
+    Ok(digits.chunks(2).map(|pair| (pair[0], pair[1])).collect())
+}
+
+#[must_use]
+pub fn format_cells(cells: &[(usize, usize)]) -> String {
+    cells
+        .iter()
+        .flat_map(|&(row, col)| [index_char(row), index_char(col)])
+        .collect()
+}
+
+fn index_char(idx: usize) -> char {
+    match idx {
+        0..=8 => (b'1' + idx as u8) as char,
+        _ => (b'a' + idx as u8 - 9) as char,
+    }
+}
+
+impl FromStr for Variant {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut variant = Self::default();
+        for token in s.split('_').filter(|token| !token.is_empty()) {
+            let kind_len = token.chars().next().map_or(0, char::len_utf8);
+            match token.split_at(kind_len) {
+                _ if token == "knight" => variant.anti_knight = true,
+                _ if token == "king" => variant.anti_king = true,
+                ("s", side) => {
+                    variant.size = Size::ALL
+                        .into_iter()
+                        .find(|size| size.digits().to_string() == side)
+                        .ok_or(Error::ParseVariant)?;
+                }
+                ("x", "") => variant.diagonal = true,
+                ("k", cage) => variant.cages.push(cage.parse()?),
+                ("t", thermo) => variant.thermos.push(thermo.parse()?),
+                ("a", arrow) => variant.arrows.push(arrow.parse()?),
+                ("w", dot) => variant.dots.push(Dot::parse(DotKind::White, dot)?),
+                ("b", dot) => variant.dots.push(Dot::parse(DotKind::Black, dot)?),
+                ("j", regions) => {
+                    variant.regions = regions
+                        .chars()
+                        .map(|c| parse_index(c).and_then(|idx| u8::try_from(idx).ok()))
+                        .collect::<Option<_>>()
+                        .ok_or(Error::ParseVariant)?;
+                }
+                _ => return Err(Error::ParseVariant),
+            }
+        }
+        variant.check_cells()?;
+        Ok(variant)
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = (self.size != Size::default()).then(|| format!("s{}", self.size.digits()));
+        let diagonal = self.diagonal.then(|| "x".to_string());
+        let knight = self.anti_knight.then(|| "knight".to_string());
+        let king = self.anti_king.then(|| "king".to_string());
+        let cages = self.cages.iter().map(|cage| format!("k{cage}"));
+        let thermos = self.thermos.iter().map(|thermo| format!("t{thermo}"));
+        let arrows = self.arrows.iter().map(|arrow| format!("a{arrow}"));
+        let dots = self.dots.iter().map(|dot| match dot.kind {
+            DotKind::White => format!("w{dot}"),
+            DotKind::Black => format!("b{dot}"),
+        });
+        let regions = (!self.regions.is_empty()).then(|| {
+            let regions: String = self
+                .regions
+                .iter()
+                .map(|&region| index_char(usize::from(region)))
+                .collect();
+            format!("j{regions}")
+        });
+        let tokens: Vec<String> = size
+            .into_iter()
+            .chain(diagonal)
+            .chain(knight)
+            .chain(king)
+            .chain(cages)
+            .chain(thermos)
+            .chain(arrows)
+            .chain(dots)
+            .chain(regions)
+            .collect();
+        write!(f, "{}", tokens.join("_"))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_roundtrip() -> Result<()> {
+        let input = "x_k10-1112_k15-132324";
+        let variant = Variant::from_str(input)?;
+        assert!(variant.diagonal);
+        assert_eq!(variant.cages.len(), 2);
+        assert_eq!(variant.to_string(), input);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_variant() {
+        assert!(Variant::from_str("k10-111").is_err());
+        assert!(Variant::from_str("k10-1102").is_err());
+        assert!(Variant::from_str("q").is_err());
+        assert!(Variant::from_str("x1").is_err());
+        assert!(Variant::from_str("s5").is_err());
+        assert!(Variant::from_str("s4_k3-1115").is_err());
+        assert!(Variant::from_str("s4_t1115").is_err());
+        assert!(Variant::from_str("w1113").is_err());
+    }
+
+    #[test]
+    fn test_conflicts() -> Result<()> {
+        let variant = Variant::from_str("knight_k5-1112")?;
+        let mut digits = vec![0; 81];
+        digits[0] = 4;
+        digits[11] = 4;
+        assert_eq!(variant.conflicts(&digits), vec![(0, 0), (1, 2)]);
+        digits[11] = 0;
+        digits[1] = 2;
+        assert_eq!(variant.conflicts(&digits), vec![(0, 0), (0, 1)]);
+        digits[1] = 1;
+        assert!(variant.conflicts(&digits).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cage_peers() -> Result<()> {
+        let variant = Variant::from_str("k10-3344")?;
+        assert!(variant.are_peers((2, 2), (3, 3)));
+        assert!(variant.peers(2, 2).contains(&(3, 3)));
+        assert!(!variant.are_peers((2, 2), (4, 4)));
+        assert_eq!(Variant::default().peers(0, 0).len(), 20);
+        Ok(())
+    }
+
+    #[test]
+    fn test_peer_table_is_reused() -> Result<()> {
+        let variant = Variant::from_str("x")?;
+        let peers = variant.peer_table();
+        assert!(Rc::ptr_eq(&peers, &variant.clone().peer_table()));
+        assert!(!Rc::ptr_eq(&peers, &Variant::default().peer_table()));
+        assert_eq!(peers[0], variant.peers(0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagonal_peers() -> Result<()> {
+        let variant = Variant::from_str("x")?;
+        assert!(variant.are_peers((0, 0), (8, 8)));
+        assert!(variant.are_peers((0, 8), (4, 4)));
+        assert!(!variant.are_peers((0, 0), (8, 1)));
+        assert_eq!(variant.peers(0, 0).len(), 26);
+        assert_eq!(variant.peers(4, 4).len(), 32);
+        assert_eq!(variant.diagonals_at(4, 4), (true, true));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sized_variant() -> Result<()> {
+        let variant = Variant::from_str("s16_x_k3-gfgg")?;
+        assert_eq!(variant.size, Size::Sixteen);
+        assert_eq!(variant.to_string(), "s16_x_k3-gfgg");
+        assert_eq!(variant.cages[0].cells, vec![(15, 14), (15, 15)]);
+        assert_eq!(variant.houses().len(), 3 * 16 + 2 + 1);
+        assert!(variant.are_peers((0, 15), (15, 0)));
+
+        let six = Variant::from_str("s6")?;
+        assert!(six.are_peers((0, 0), (1, 2)));
+        assert!(!six.are_peers((0, 0), (2, 1)));
+        assert_eq!(six.peers(0, 0).len(), 5 + 5 + 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_chess_peers() -> Result<()> {
+        let variant = Variant::from_str("knight_king")?;
+        assert_eq!(variant.to_string(), "knight_king");
+        assert!(!variant.is_classic());
+
+        let knight = Variant::from_str("knight")?;
+        assert!(knight.are_peers((4, 4), (2, 3)));
+        assert!(knight.are_peers((4, 4), (5, 6)));
+        assert!(!knight.are_peers((2, 2), (3, 3)));
+        assert_eq!(knight.peers(4, 4).len(), 28);
+
+        let king = Variant::from_str("king")?;
+        assert!(king.are_peers((2, 2), (3, 3)));
+        assert!(!king.are_peers((2, 2), (4, 3)));
+        assert_eq!(king.peers(4, 4).len(), 20);
+        assert_eq!(king.peers(2, 2).len(), 23);
+        assert!(Variant::from_str("kings").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_variant() -> Result<()> {
+        let input = "t111213_a212232_w3132_b4142";
+        let variant = Variant::from_str(input)?;
+        assert_eq!(variant.to_string(), input);
+        assert!(!variant.is_classic());
+        assert!(variant.check_classic_techniques().is_ok());
+        assert!(variant.are_peers((0, 0), (0, 2)));
+        assert!(variant.are_peers((2, 0), (2, 1)));
+
+        let mut digits = vec![0; 81];
+        digits[27] = 3;
+        digits[28] = 5;
+        assert_eq!(variant.conflicts(&digits), vec![(3, 0), (3, 1)]);
+        Ok(())
+    }
+
+    const JIGSAW: &str =
+        "111222236111222236441533236411553336444555366744555669774889699778889999777788889";
+
+    #[test]
+    fn test_box_regions() -> Result<()> {
+        for size in Size::ALL {
+            let variant = Variant {
+                size,
+                ..Variant::default()
+            };
+            let regions = variant.regions();
+            assert!(regions.iter().all(|cells| cells.len() == size.digits()));
+        }
+        let six = Variant::from_str("s6")?.regions();
+        assert_eq!(six[1], vec![(0, 3), (0, 4), (0, 5), (1, 3), (1, 4), (1, 5)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_jigsaw_variant() -> Result<()> {
+        let input = format!("j{JIGSAW}");
+        let variant = Variant::from_str(&input)?;
+        assert_eq!(variant.to_string(), input);
+        assert!(!variant.is_classic());
+        assert!(variant.are_peers((0, 0), (1, 2)));
+        assert!(!variant.are_peers((0, 3), (1, 2)));
+        assert!(variant.are_peers((0, 7), (3, 6)));
+        assert_eq!(variant.region_borders(0, 0), [false; 4]);
+        assert_eq!(variant.region_borders(0, 2), [false, true, false, false]);
+        assert_eq!(variant.peers(0, 0).len(), 21);
+        assert_eq!(variant.peers(0, 2).len(), 19);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_jigsaw() {
+        let disconnected = format!("j211221236{}", &JIGSAW[9..]);
+        assert!(Variant::from_str(&disconnected).is_err());
+        assert!(Variant::from_str(&format!("j{}", &JIGSAW[1..])).is_err());
+        assert!(Variant::from_str(&format!("j{}1", &JIGSAW[1..])).is_err());
+        assert!(Variant::from_str("j111").is_err());
+    }
+
+    #[test]
+    fn test_cage_borders() -> Result<()> {
+        let variant = Variant::from_str("k10-111221")?;
+        assert_eq!(variant.cage_borders(0, 0), Some([true, false, false, true]));
+        assert_eq!(variant.cage_borders(0, 1), Some([true, true, true, false]));
+        assert_eq!(variant.cage_borders(1, 0), Some([false, true, true, true]));
+        assert_eq!(variant.cage_borders(1, 1), None);
+        Ok(())
+    }
+}
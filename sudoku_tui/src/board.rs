@@ -0,0 +1,220 @@
+use ratatui::{
+    style::{Color as TermColor, Modifier, Style},
+    text::{Line, Span},
+};
+use sudoku_core::{
+    size::Size,
+    state::Highlight,
+    sudoku_data::{Cell, CellMarks, Color, SudokuData},
+};
+
+use crate::game::Game;
+
+pub const fn cell_width(size: Size) -> usize {
+    2 * size.box_cols() + 1
+}
+
+pub fn board_lines(game: &Game) -> Vec<Line<'static>> {
+    let size = game.sudoku.size();
+    let n = size.digits();
+    let mut lines = Vec::new();
+    for row in 0..n {
+        lines.extend((0..size.box_rows()).map(|k| cell_line(game, row, k)));
+        if row + 1 < n {
+            lines.push(border_line(&game.sudoku, row));
+        }
+    }
+    lines
+}
+
+fn cell_line(game: &Game, row: usize, k: usize) -> Line<'static> {
+    let n = game.sudoku.size().digits();
+    let mut spans = Vec::new();
+    for col in 0..n {
+        spans.extend(cell_spans(game, row, col, k));
+        if col + 1 < n {
+            let border = if splits_cols(&game.sudoku, row, col) {
+                "│"
+            } else {
+                " "
+            };
+            spans.push(Span::raw(border));
+        }
+    }
+    Line::from(spans)
+}
+
+fn border_line(sudoku: &SudokuData, row: usize) -> Line<'static> {
+    let n = sudoku.size().digits();
+    let width = cell_width(sudoku.size());
+    let mut text = String::new();
+    for col in 0..n {
+        let below = splits_rows(sudoku, row, col);
+        text.push_str(&(if below { "─" } else { " " }).repeat(width));
+        if col + 1 < n {
+            let across = below || splits_rows(sudoku, row, col + 1);
+            let along = splits_cols(sudoku, row, col) || splits_cols(sudoku, row + 1, col);
+            text.push(joint(across, along));
+        }
+    }
+    Line::from(text)
+}
+
+fn splits_cols(sudoku: &SudokuData, row: usize, col: usize) -> bool {
+    sudoku.variant.region(row, col) != sudoku.variant.region(row, col + 1)
+}
+
+fn splits_rows(sudoku: &SudokuData, row: usize, col: usize) -> bool {
+    sudoku.variant.region(row, col) != sudoku.variant.region(row + 1, col)
+}
+
+const fn joint(across: bool, along: bool) -> char {
+    match (across, along) {
+        (true, true) => '┼',
+        (true, false) => '─',
+        (false, true) => '│',
+        (false, false) => ' ',
+    }
+}
+
+fn cell_spans(game: &Game, row: usize, col: usize, k: usize) -> Vec<Span<'static>> {
+    let style = cell_style(game, row, col);
+    match game.sudoku.get(row, col) {
+        Cell::Empty { choices } => candidate_spans(game, (row, col), k, &choices, style),
+        cell if game.state.changes.is_placed((row, col)) => {
+            let style = style.add_modifier(Modifier::REVERSED);
+            vec![value_span(game.sudoku.size(), &cell, k, style)]
+        }
+        cell => vec![value_span(game.sudoku.size(), &cell, k, style)],
+    }
+}
+
+fn value_span(size: Size, cell: &Cell, k: usize, style: Style) -> Span<'static> {
+    let width = cell_width(size);
+    let Some(value) = cell.value().filter(|_| k == size.box_rows() / 2) else {
+        return Span::styled(" ".repeat(width), style);
+    };
+    let style = match cell {
+        Cell::FixedValue { .. } => style.add_modifier(Modifier::BOLD),
+        Cell::Error { .. } => style.fg(TermColor::Red),
+        Cell::Empty { .. } | Cell::Value { .. } | Cell::AnimatedValue { .. } => {
+            style.fg(TermColor::Cyan)
+        }
+    };
+    Span::styled(format!("{:^width$}", size.digit_char(value)), style)
+}
+
+fn candidate_spans(
+    game: &Game,
+    (row, col): (usize, usize),
+    k: usize,
+    choices: &[bool],
+    style: Style,
+) -> Vec<Span<'static>> {
+    let size = game.sudoku.size();
+    let marks = game.sudoku.marks(row, col);
+    let mut spans: Vec<_> = (0..size.box_cols())
+        .map(|i| {
+            let digit = (k * size.box_cols() + i + 1) as u8;
+            let idx = usize::from(digit - 1);
+            let shown = if marks.has_pencil_marks() {
+                marks.corner[idx] || marks.centre[idx]
+            } else {
+                choices[idx]
+            };
+            let eliminated = game.state.changes.is_eliminated((row, col), digit);
+            let text = if shown || eliminated {
+                format!(" {}", size.digit_char(digit))
+            } else {
+                "  ".to_string()
+            };
+            let style = if eliminated && !shown {
+                style.fg(TermColor::Red).add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                candidate_style(game, marks, digit, style)
+            };
+            Span::styled(text, style)
+        })
+        .collect();
+    spans.push(Span::styled(" ", style));
+    spans
+}
+
+fn candidate_style(game: &Game, marks: &CellMarks, digit: u8, style: Style) -> Style {
+    let painted = marks.choice_colors[usize::from(digit - 1)];
+    if game.state.is_highlighted_choice(&game.sudoku, digit) {
+        style.fg(TermColor::Yellow).add_modifier(Modifier::BOLD)
+    } else if let Some(color) = painted {
+        style.fg(term_color(color))
+    } else if marks.has_pencil_marks() {
+        style.fg(TermColor::Green)
+    } else {
+        style.fg(TermColor::DarkGray)
+    }
+}
+
+fn cell_style(game: &Game, row: usize, col: usize) -> Style {
+    let highlight = game.state.highlight(&game.sudoku, row, col);
+    let painted = game.sudoku.marks(row, col).color.map(term_color);
+    let bg = match (highlight, painted) {
+        (Highlight::Active, _) => TermColor::Indexed(25),
+        (Highlight::Selected, _) => TermColor::Indexed(24),
+        (_, Some(color)) => color,
+        (Highlight::SameDigit, None) => TermColor::Indexed(58),
+        (Highlight::Peer, None) => TermColor::Indexed(236),
+        (Highlight::None, None) => TermColor::Reset,
+    };
+    Style::default().bg(bg)
+}
+
+pub const fn term_color(color: Color) -> TermColor {
+    match color {
+        Color::Red => TermColor::Red,
+        Color::Orange => TermColor::Indexed(208),
+        Color::Yellow => TermColor::Yellow,
+        Color::Green => TermColor::Green,
+        Color::Blue => TermColor::Blue,
+        Color::Purple => TermColor::Magenta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sudoku_core::{puzzle::Puzzle, variant::Variant};
+
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_board_layout() {
+        let game = Game::default();
+        let lines = board_lines(&game);
+        // three lines for each cell and one between each row
+        assert_eq!(lines.len(), 9 * 3 + 8);
+        let width = 9 * cell_width(Size::Nine) + 8;
+        assert!(lines.iter().all(|line| text(line).chars().count() == width));
+        assert!(text(&lines[0]).starts_with(" 1 2 3   1 2 3   1 2 3 │"));
+        assert!(!text(&lines[3]).contains('─'));
+        assert!(text(&lines[11]).starts_with(&format!("{}┼", "─".repeat(23))));
+    }
+
+    #[test]
+    fn test_placed_digit() {
+        let game = Game::from(&Puzzle {
+            givens: [vec![3], vec![0; 15]].concat(),
+            variant: Variant {
+                size: Size::Four,
+                ..Variant::default()
+            },
+        });
+        let lines = board_lines(&game);
+        assert_eq!(lines.len(), 4 * 2 + 3);
+        assert!(text(&lines[1]).starts_with("  3  "));
+    }
+}
@@ -7,18 +7,21 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug, From)]
 pub enum Error {
     #[from]
-    Sudoku(rust_sudoku_solver::Error),
+    Io(std::io::Error),
     #[from]
-    Utf8(std::string::FromUtf8Error),
-    GenerateSudoku,
+    Json(serde_json::Error),
+    #[from]
+    Sudoku(sudoku_core::Error),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "Invalid saved game: {e}"),
             Self::Sudoku(e) => write!(f, "{e}"),
-            Self::Utf8(e) => write!(f, "Invalid UTF-8: {e}"),
-            Self::GenerateSudoku => write!(f, "Failed to generate sudoku"),
         }
     }
 }
+
+impl std::error::Error for Error {}
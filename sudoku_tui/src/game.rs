@@ -0,0 +1,221 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use sudoku_core::{
+    actions::{
+        apply_digit, check_selected_cells, clear_colors_if_selected, clear_digit_if_selected,
+        reveal_active_cell, toggle_color_if_selected,
+    },
+    bindings::{
+        get_color_hotkeys, get_generator_hotkeys, get_solver_hotkeys, CHECK_CELLS_KEY,
+        CLEAR_COLORS_KEY, REVEAL_CELL_KEY,
+    },
+    diff::Changes,
+    generator::{get_random_sudoku, Difficulty},
+    puzzle::Puzzle,
+    size::Size,
+    state::{DigitMode, GameState, Tally},
+    sudoku_data::SudokuData,
+    techniques::{find_solution, update_from_digits},
+    Error, Result,
+};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Game {
+    pub sudoku: SudokuData,
+    pub state: GameState,
+    #[serde(skip)]
+    pub mode: DigitMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Quit,
+}
+
+impl From<&Puzzle> for Game {
+    fn from(puzzle: &Puzzle) -> Self {
+        let mut sudoku = SudokuData::new(puzzle.variant.clone());
+        update_from_digits(&mut sudoku, &puzzle.givens, true);
+        let mut state = GameState::default();
+        state.select((0, 0));
+        Self {
+            sudoku,
+            state,
+            mode: DigitMode::default(),
+        }
+    }
+}
+
+impl Game {
+    pub fn random(difficulty: Difficulty) -> Result<Self> {
+        if matches!(difficulty, Difficulty::Samurai) {
+            return Err(Error::UnsupportedSize);
+        }
+        let (givens, variant) = get_random_sudoku(difficulty)?;
+        let givens = variant.size.parse(&givens).ok_or(Error::GenerateSudoku)?;
+        Ok(Self::from(&Puzzle { givens, variant }))
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Flow {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('c' | 'q') => Flow::Quit,
+                _ => Flow::Continue,
+            };
+        }
+        // the changes of a technique stay highlighted until the next key
+        self.state.changes = Changes::default();
+        let extend = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Tab => self.mode.toggle(),
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Delete => {
+                clear_digit_if_selected(&self.state, &mut self.sudoku);
+            }
+            KeyCode::Up => self.move_active((-1, 0), extend),
+            KeyCode::Down => self.move_active((1, 0), extend),
+            KeyCode::Left => self.move_active((0, -1), extend),
+            KeyCode::Right => self.move_active((0, 1), extend),
+            KeyCode::Char(c) => self.handle_char(c),
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn move_active(&mut self, direction: (i32, i32), extend: bool) {
+        self.state
+            .move_active(self.sudoku.size(), direction, extend);
+    }
+
+    fn handle_char(&mut self, c: char) {
+        let size = self.sudoku.size();
+        let letter_digit = size == Size::Sixteen && c.is_ascii_hexdigit() && c.is_ascii_lowercase();
+        if c.is_ascii_digit() || letter_digit {
+            if let Some(digit) = size.parse_digit(c) {
+                apply_digit(self.mode, &self.state, &mut self.sudoku, digit);
+            }
+            return;
+        }
+        let key = c.to_ascii_uppercase().to_string();
+        if !self.apply_hint_hotkey(&key)
+            && !self.apply_solver_hotkey(&key)
+            && !self.apply_generator_hotkey(&key)
+        {
+            self.apply_color_hotkey(&key);
+        }
+    }
+
+    fn apply_hint_hotkey(&mut self, key: &str) -> bool {
+        let hint = match key {
+            REVEAL_CELL_KEY => reveal_active_cell,
+            CHECK_CELLS_KEY => check_selected_cells,
+            _ => return false,
+        };
+        let solution = find_solution(&self.sudoku.fixed_digits(), &self.sudoku.variant);
+        let result = solution.map(|solution| hint(&mut self.state, &mut self.sudoku, &solution));
+        self.state.show_result(result);
+        true
+    }
+
+    fn apply_solver_hotkey(&mut self, key: &str) -> bool {
+        let Some(hotkey) = get_solver_hotkeys().into_iter().find(|h| h.key == key) else {
+            return false;
+        };
+        let before = self.sudoku.clone();
+        let result = (hotkey.on_click)(&mut self.sudoku);
+        self.state
+            .show_changes(result, Changes::between(&before, &self.sudoku));
+        true
+    }
+
+    fn apply_generator_hotkey(&mut self, key: &str) -> bool {
+        let Some(hotkey) = get_generator_hotkeys().into_iter().find(|h| h.key == key) else {
+            return false;
+        };
+        match Self::random(hotkey.difficulty) {
+            Ok(game) => {
+                self.sudoku = game.sudoku;
+                self.state.select((0, 0));
+                self.state.message = None;
+                self.state.tally = Tally::default();
+            }
+            Err(e) => self.state.message = Some(e.to_string()),
+        }
+        true
+    }
+
+    fn apply_color_hotkey(&mut self, key: &str) -> bool {
+        if key == CLEAR_COLORS_KEY {
+            clear_colors_if_selected(&self.state, &mut self.sudoku);
+            return true;
+        }
+        let Some(hotkey) = get_color_hotkeys().into_iter().find(|h| h.key == key) else {
+            return false;
+        };
+        toggle_color_if_selected(&mut self.state, &mut self.sudoku, hotkey.color);
+        true
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic_in_result_fn)]
+mod tests {
+    use std::str::FromStr;
+
+    use sudoku_core::{generator::HARD_SUDOKUS, sudoku_data::Color, variant::Variant};
+
+    use super::*;
+
+    fn press(game: &mut Game, code: KeyCode, modifiers: KeyModifiers) -> Flow {
+        game.handle_key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn test_keys() -> Result<()> {
+        let mut game = Game::from(&Puzzle::from_str(HARD_SUDOKUS[0])?);
+        press(&mut game, KeyCode::Right, KeyModifiers::NONE);
+        press(&mut game, KeyCode::Char('8'), KeyModifiers::NONE);
+        assert_eq!(game.sudoku.get(0, 1).value(), Some(8));
+        press(&mut game, KeyCode::Backspace, KeyModifiers::NONE);
+        assert!(game.sudoku.get(0, 1).is_empty());
+
+        press(&mut game, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(game.sudoku.marks(0, 1).color, Some(Color::Red));
+
+        press(&mut game, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(game.state.message.is_some());
+        assert_eq!(
+            press(&mut game, KeyCode::Char('q'), KeyModifiers::CONTROL),
+            Flow::Quit
+        );
+
+        // on 16×16 boards a to f are digits, and the hotkeys take Shift
+        let mut game = Game::from(&Puzzle {
+            givens: vec![0; 256],
+            variant: Variant::from_str("s16")?,
+        });
+        press(&mut game, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert_eq!(game.sudoku.get(0, 0).value(), Some(11));
+        press(&mut game, KeyCode::Right, KeyModifiers::NONE);
+        press(&mut game, KeyCode::Char('f'), KeyModifiers::NONE);
+        assert_eq!(game.sudoku.get(0, 1).value(), Some(16));
+        press(&mut game, KeyCode::Char('Q'), KeyModifiers::SHIFT);
+        assert_eq!(game.sudoku.marks(0, 1).color, Some(Color::Red));
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_key() -> Result<()> {
+        let mut game = Game::from(&Puzzle::from_str(HARD_SUDOKUS[0])?);
+        press(&mut game, KeyCode::Char('H'), KeyModifiers::SHIFT);
+        assert!(game.sudoku.digits().iter().all(|&digit| digit != 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_samurai_is_not_loaded() {
+        let mut game = Game::default();
+        press(&mut game, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(game.state.message.is_some());
+    }
+}
@@ -0,0 +1,118 @@
+#![warn(
+    // missing_docs,
+    // unreachable_pub,
+    keyword_idents,
+    unexpected_cfgs,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    unused_crate_dependencies,
+    unused_extern_crates,
+    unused_import_braces,
+    future_incompatible,
+    nonstandard_style,
+    bad_style,
+    dead_code,
+    improper_ctypes,
+    non_shorthand_field_patterns,
+    no_mangle_generic_items,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true,
+)]
+
+use std::io::{self, Write};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use clap::Parser;
+use error::Result;
+use game::{Flow, Game};
+use ratatui::crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+use ratatui::DefaultTerminal;
+use sudoku_core::generator::Difficulty;
+use sudoku_core::puzzle::Puzzle;
+
+mod board;
+mod error;
+mod game;
+mod storage;
+mod ui;
+
+/// Play sudoku in the terminal, with the keys of the web app. The game is
+/// saved after every key and picked up again on the next start.
+#[derive(Debug, Parser)]
+#[command(name = "sudoku-tui", version)]
+struct Args {
+    /// Start a new game with this puzzle, written like the `?puzzle=` links
+    /// of the web app
+    puzzle: Option<String>,
+}
+
+fn start(args: &Args) -> Result<Game> {
+    if let Some(puzzle) = &args.puzzle {
+        return Ok(Game::from(&Puzzle::from_str(puzzle)?));
+    }
+    match storage::load() {
+        Ok(Some(game)) => Ok(game),
+        Ok(None) => Ok(Game::random(Difficulty::Hard)?),
+        Err(err) => {
+            let mut game = Game::random(Difficulty::Hard)?;
+            game.state.message = Some(err.to_string());
+            Ok(game)
+        }
+    }
+}
+
+fn read_key() -> io::Result<Option<KeyEvent>> {
+    Ok(match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Press => Some(key),
+        _ => None,
+    })
+}
+
+fn save(game: &mut Game) {
+    if let Err(err) = storage::save(game) {
+        game.state.message = Some(err.to_string());
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, game: &mut Game) -> Result<()> {
+    loop {
+        terminal.draw(|frame| ui::draw(frame, game))?;
+        if let Some(key) = read_key()? {
+            if game.handle_key(key) == Flow::Quit {
+                return Ok(());
+            }
+            save(game);
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let mut game = match start(&args) {
+        Ok(game) => game,
+        Err(err) => {
+            let _ = writeln!(io::stderr(), "sudoku-tui: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut game);
+    ratatui::restore();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let _ = writeln!(io::stderr(), "sudoku-tui: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
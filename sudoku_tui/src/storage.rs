@@ -0,0 +1,36 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{
+    error::{Error, Result},
+    game::Game,
+};
+
+fn save_path() -> Option<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(data_home.join("sudoku").join("game.json"))
+}
+
+pub fn load() -> Result<Option<Game>> {
+    let Some(path) = save_path().filter(|path| path.exists()) else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+}
+
+pub fn save(game: &Game) -> Result<()> {
+    let path = save_path().ok_or_else(|| {
+        Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "No home directory to save the game in",
+        ))
+    })?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string(game)?)?;
+    Ok(())
+}
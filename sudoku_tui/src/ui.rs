@@ -0,0 +1,100 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+use sudoku_core::{
+    bindings::{
+        get_color_hotkeys, get_generator_hotkeys, get_solver_hotkeys, CHECK_CELLS_KEY,
+        CLEAR_COLORS_KEY, REVEAL_CELL_KEY,
+    },
+    state::DigitMode,
+};
+
+use crate::{board, game::Game};
+
+pub fn draw(frame: &mut Frame, game: &Game) {
+    let size = game.sudoku.size();
+    let n = size.digits();
+    // the cells, the lines between them and the frame
+    let width = n * board::cell_width(size) + n + 1;
+    let height = n * size.box_rows() + n + 1;
+    let [main, side] = Layout::horizontal([Constraint::Length(width as u16), Constraint::Min(0)])
+        .areas(frame.area());
+    let [board_area, message_area] =
+        Layout::vertical([Constraint::Length(height as u16), Constraint::Min(0)]).areas(main);
+
+    let board = Paragraph::new(board::board_lines(game)).block(Block::bordered());
+    frame.render_widget(board, board_area);
+    let message =
+        Paragraph::new(game.state.message.clone().unwrap_or_default()).wrap(Wrap { trim: true });
+    frame.render_widget(message, message_area);
+    let keys = Paragraph::new(key_lines(game.mode)).block(Block::bordered().title(" KEYS "));
+    frame.render_widget(keys, side);
+}
+
+fn key_line(key: &str, action: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{key:<6}"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(action.to_string()),
+    ])
+}
+
+fn key_lines(mode: DigitMode) -> Vec<Line<'static>> {
+    let modes = DigitMode::ALL.into_iter().map(|m| {
+        let style = if m == mode {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        Span::styled(format!(" {} ", m.label()), style)
+    });
+    let mut lines = vec![
+        Line::from(
+            [Span::raw("TAB   ")]
+                .into_iter()
+                .chain(modes)
+                .collect::<Vec<_>>(),
+        ),
+        Line::default(),
+    ];
+    lines.extend(
+        get_solver_hotkeys()
+            .iter()
+            .map(|hotkey| key_line(hotkey.key, hotkey.action)),
+    );
+    lines.extend([
+        key_line(REVEAL_CELL_KEY, "REVEAL CELL"),
+        key_line(CHECK_CELLS_KEY, "CHECK CELLS"),
+        Line::default(),
+    ]);
+    lines.extend(
+        get_generator_hotkeys()
+            .iter()
+            .map(|hotkey| key_line(hotkey.key, hotkey.action)),
+    );
+    lines.push(Line::default());
+    lines.extend(get_color_hotkeys().iter().map(|hotkey| {
+        let swatch = Style::default().bg(board::term_color(hotkey.color));
+        Line::from(vec![
+            Span::styled(
+                format!("{:<6}", hotkey.key),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("   ", swatch),
+        ])
+    }));
+    lines.extend([
+        key_line(CLEAR_COLORS_KEY, "CLEAR COLORS"),
+        Line::default(),
+        key_line("ARROWS", "MOVE, WITH SHIFT TO SELECT"),
+        key_line("ESC", "CLEAR"),
+        key_line("^Q", "QUIT"),
+    ]);
+    lines
+}
@@ -0,0 +1,66 @@
+#![warn(
+    // missing_docs,
+    // unreachable_pub,
+    keyword_idents,
+    unexpected_cfgs,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    unused_crate_dependencies,
+    unused_extern_crates,
+    unused_import_braces,
+    future_incompatible,
+    nonstandard_style,
+    bad_style,
+    dead_code,
+    improper_ctypes,
+    non_shorthand_field_patterns,
+    no_mangle_generic_items,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true,
+)]
+
+use js_sys::global;
+use sudoku_core::protocol::{handle, Request, READY};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{console, DedicatedWorkerGlobalScope, MessageEvent};
+
+fn answer(scope: &DedicatedWorkerGlobalScope, event: &MessageEvent) {
+    let request = event
+        .data()
+        .as_string()
+        .and_then(|json| serde_json::from_str::<Request>(&json).ok());
+    let Some(request) = request else {
+        console::error_1(&"Invalid request".into());
+        return;
+    };
+    let sent = serde_json::to_string(&handle(request))
+        .ok()
+        .map(|json| scope.post_message(&JsValue::from_str(&json)));
+    if !matches!(sent, Some(Ok(()))) {
+        console::error_1(&"Failed to send response".into());
+    }
+}
+
+fn main() {
+    console_error_panic_hook::set_once();
+    let scope: DedicatedWorkerGlobalScope = global().unchecked_into();
+    let onmessage = {
+        let scope = scope.clone();
+        Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| answer(&scope, &event))
+    };
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    // the handler lives as long as the worker
+    onmessage.forget();
+    if scope.post_message(&JsValue::from_str(READY)).is_err() {
+        console::error_1(&"Failed to start worker".into());
+    }
+}